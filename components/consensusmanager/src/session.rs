@@ -10,7 +10,7 @@ use kaspa_consensus_core::{
     daa_score_timestamp::DaaScoreTimestamp,
     errors::consensus::ConsensusResult,
     header::Header,
-    pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
+    pruning::{PruningInfo, PruningPointProof, PruningPointTrustedData, PruningPointsList},
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
     BlockHashSet, BlueWorkType, ChainPath, Hash,
@@ -225,6 +225,23 @@ impl ConsensusSessionOwned {
         self.consensus.get_virtual_parents_len()
     }
 
+    pub fn set_pruning_allowed(&self, allowed: bool) {
+        // Toggling the pruning gate is a lock-free atomic store and does not require spawn_blocking
+        self.consensus.set_pruning_allowed(allowed)
+    }
+
+    pub async fn async_get_pruning_info(&self) -> PruningInfo {
+        self.clone().spawn_blocking(|c| c.get_pruning_info()).await
+    }
+
+    pub async fn async_is_pruned(&self, hash: Hash) -> bool {
+        self.clone().spawn_blocking(move |c| c.is_pruned(hash)).await
+    }
+
+    pub async fn async_prune_to_current_point(&self) -> bool {
+        self.clone().spawn_blocking(|c| c.prune_to_current_point()).await
+    }
+
     pub async fn async_get_stats(&self) -> ConsensusStats {
         self.clone().spawn_blocking(|c| c.get_stats()).await
     }
@@ -17,7 +17,7 @@ use crate::{
         tx::TxResult,
     },
     header::Header,
-    pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
+    pruning::{PruningInfo, PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
     BlockHashSet, BlueWorkType, ChainPath,
@@ -332,6 +332,11 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns the current pruning point, candidate and index along with the last pruned marker, read as a consistent snapshot
+    fn get_pruning_info(&self) -> PruningInfo {
+        unimplemented!()
+    }
+
     // TODO: Delete this function once there's no need for go-kaspad backward compatibility.
     fn get_daa_window(&self, hash: Hash) -> ConsensusResult<Vec<Hash>> {
         unimplemented!()
@@ -362,6 +367,26 @@ pub trait ConsensusApi: Send + Sync {
     fn finality_point(&self) -> Hash {
         unimplemented!()
     }
+
+    /// Opens or closes the pruning gate. While closed (e.g. during IBD), the pruning point keeps advancing
+    /// but the deletion of data in its past is deferred until the gate is reopened
+    fn set_pruning_allowed(&self, allowed: bool) {
+        unimplemented!()
+    }
+
+    /// Returns whether the data of `hash` was removed by pruning, as opposed to the block being unknown. Note that unless
+    /// all headers are retained, fully pruned blocks (other than past pruning points) cannot be told from unknown ones
+    fn is_pruned(&self, hash: Hash) -> bool {
+        unimplemented!()
+    }
+
+    /// Requests a data prune up to the current pruning point, without advancing it. Useful for deleting data which became
+    /// prunable (e.g., following a switch from archival mode) while no pruning point movement occurs. The request is recorded
+    /// without blocking and the prune runs asynchronously by the pruning processor. Returns whether the request was accepted,
+    /// i.e., `false` if consensus is exiting
+    fn prune_to_current_point(&self) -> bool {
+        unimplemented!()
+    }
 }
 
 pub type DynConsensus = Arc<dyn ConsensusApi>;
@@ -10,7 +10,10 @@ use crate::utxo::utxo_collection::UtxoCollection;
 #[cfg(feature = "devnet-prealloc")]
 use std::sync::Arc;
 
-use std::ops::Deref;
+use std::{ops::Deref, time::Duration};
+
+/// A disk throughput measured in bytes per second
+pub type BytesPerSec = u64;
 
 use {
     constants::perf::{PerfParams, PERF_PARAMS},
@@ -35,9 +38,109 @@ pub struct Config {
     /// Indicates whether this node is an archival node
     pub is_archival: bool,
 
-    /// Enable various sanity checks which might be compute-intensive (mostly performed during pruning)
+    /// Enable various sanity checks which might be compute-intensive (mostly performed during pruning).
+    /// Acts as a shorthand for enabling all `pruning_sanity_checks`
     pub enable_sanity_checks: bool,
 
+    /// Selects which pruning sanity checks to perform when `enable_sanity_checks` is off
+    pub pruning_sanity_checks: PruningSanityChecks,
+
+    /// The accumulated size (in bytes) of a pruning deletion batch above which it is flushed to the DB.
+    /// A zero threshold flushes following every pruned block
+    pub pruning_batch_flush_threshold: usize,
+
+    /// The minimal number of traversed blocks whose deletions are grouped into a single pruning batch, i.e., whose relations
+    /// and reachability changes are staged together and committed once. This caps the frequency of staging commits, which
+    /// otherwise dominate the IO of mass prunes. A batch is still flushed early when the lock must be yielded to consensus
+    pub pruning_min_blocks_per_commit: usize,
+
+    /// The maximal number of traversed blocks whose deletions are grouped into a single pruning batch (unbounded if unset).
+    /// Unlike the minimum, this bound is never exceeded, and it takes precedence over the minimum if lower. Bounds the
+    /// memory held by the staging stores and the time the reachability lock is held by a single batch
+    pub pruning_max_blocks_per_commit: Option<usize>,
+
+    /// Verify on startup that the past pruning points form a contiguous chain up to the current pruning point
+    pub verify_past_pruning_points_on_startup: bool,
+
+    /// An optional bound on the write throughput of data pruning. When set, the pruning traversal sleeps
+    /// (without holding the pruning lock) in between flushed batches in order to stay under the budget
+    pub pruning_io_budget: Option<BytesPerSec>,
+
+    /// Following the pruning traversal, additionally sweep the statuses store for orphaned blocks below the
+    /// pruning point which are not reachable from ORIGIN (e.g., leftovers of an interrupted pruning cycle).
+    /// Always performed when `pruning_db_writer_threads` is set (see `effective_pruning_orphan_sweep`)
+    pub pruning_orphan_sweep: bool,
+
+    /// Following a completed pruning cycle, manually compact the key ranges of the stores most heavily deleted from (block
+    /// bodies, acceptance data, UTXO diffs, headers and reachability) so that disk space is reclaimed promptly. Compaction
+    /// is IO-heavy, hence this is off by default
+    pub pruning_post_compaction: bool,
+
+    /// An optional bound on the in-memory length of the pruning traversal queue. Above it, queued blocks are
+    /// spilled to the DB and read back as the in-memory portion drains
+    pub pruning_queue_spill_threshold: Option<usize>,
+
+    /// Retain the headers of all pruned blocks while still pruning their bodies and UTXO related data. Similar to past pruning
+    /// points, retained headers are left with no block status and are only served by header lookups. This mode sits between
+    /// full pruning and archival, e.g., for serving header chains
+    pub retain_all_headers: bool,
+
+    /// Keep the difficulty and past median time window cache entries of blocks which are kept by pruning (the pruning
+    /// point anticone). Otherwise, window cache entries are evicted for every block visited by the pruning traversal
+    pub retain_kept_window_caches: bool,
+
+    /// An optional depth (in blue score units) below the pruning point within which the acceptance data of pruned blocks
+    /// is retained, e.g., for explorers. All other data of such blocks is pruned as usual
+    pub acceptance_data_retention: Option<u64>,
+
+    /// An optional bound on the number of pruning point advances applied by a single pruning point movement. Further
+    /// advances (e.g., following a long downtime) are left for subsequent movements, which bounds the UTXO diff range and
+    /// the data prune of any single pruning cycle
+    pub max_pruning_advances_per_cycle: Option<usize>,
+
+    /// Verify the UTXO commitment of each new pruning point by folding the applied chain UTXO diffs into the stored UTXO
+    /// multiset of the previous UTXO set position, rather than by re-scanning the entire pruning point UTXO set. Falls
+    /// back to the full scan if the multiset of the previous position is unavailable
+    pub incremental_utxo_commitment_check: bool,
+
+    /// The number of chain block UTXO diffs read ahead (concurrently) while the pruning point UTXO set is being advanced.
+    /// Diffs are still applied sequentially in chain order
+    pub pruning_utxo_diffs_readahead: usize,
+
+    /// Whether the existence of the UTXO diffs of all chain blocks in a pruning point movement range is verified before any
+    /// store is mutated, so that a missing diff (e.g., due to prior corruption) aborts the movement cleanly rather than
+    /// panicking after the pruning point UTXO set was partially advanced. Costs an extra (concurrent) existence check pass
+    pub pruning_utxo_diffs_precheck: bool,
+
+    /// The number of times a failed pruning DB write is retried before the pruning operation is abandoned
+    pub pruning_db_write_retries: u32,
+
+    /// The delay before the first retry of a failed pruning DB write. The delay is doubled following every further failure
+    pub pruning_db_write_backoff: Duration,
+
+    /// The number of dedicated threads writing pruning traversal batches in the background. The staged relations and
+    /// reachability changes of each batch are still written synchronously, so only the deletions of block data (and other
+    /// order independent per-block writes) are offloaded. Zero (the default) writes each batch atomically on the pruning thread.
+    ///
+    /// Since offloaded deletions are committed after the reachability changes which detach the deleted blocks, a run which is
+    /// interrupted in between leaves data of blocks which are no longer reachable from ORIGIN, and hence are not revisited
+    /// by later traversals. The orphan sweep, which collects such leftovers, is therefore enabled whenever this is set
+    pub pruning_db_writer_threads: usize,
+
+    /// The number of recent data pruning runs whose statistics are persisted (see `PruningProcessor::recent_pruning_stats`).
+    /// Zero disables the history
+    pub pruning_stats_history_length: usize,
+
+    /// An optional soft deadline for the pruning traversal. Once exceeded, the traversal stops at the next batch boundary and
+    /// the remaining deletions are deferred to the next processing message, which resumes the traversal from its persisted
+    /// frontier (unless the pruning point moved in the meantime). Pruning point movements are not affected
+    pub pruning_soft_deadline: Option<Duration>,
+
+    /// The pruning traversal logs its progress every `pruning_progress_log_interval` traversed blocks (zero disables the
+    /// count-based trigger) or every `pruning_progress_log_period` (if set), whichever comes first
+    pub pruning_progress_log_interval: u64,
+    pub pruning_progress_log_period: Option<Duration>,
+
     // TODO: move non-consensus parameters like utxoindex to a higher scoped Config
     /// Enable the UTXO index
     pub utxoindex: bool,
@@ -82,6 +185,29 @@ impl Config {
             process_genesis: true,
             is_archival: false,
             enable_sanity_checks: false,
+            pruning_sanity_checks: PruningSanityChecks::none(),
+            pruning_batch_flush_threshold: 0,
+            pruning_min_blocks_per_commit: 1,
+            pruning_max_blocks_per_commit: None,
+            verify_past_pruning_points_on_startup: false,
+            pruning_io_budget: None,
+            pruning_orphan_sweep: false,
+            pruning_post_compaction: false,
+            pruning_queue_spill_threshold: None,
+            retain_all_headers: false,
+            retain_kept_window_caches: false,
+            acceptance_data_retention: None,
+            max_pruning_advances_per_cycle: None,
+            incremental_utxo_commitment_check: false,
+            pruning_utxo_diffs_readahead: 16,
+            pruning_utxo_diffs_precheck: true,
+            pruning_db_write_retries: 3,
+            pruning_db_write_backoff: Duration::from_millis(100),
+            pruning_db_writer_threads: 0,
+            pruning_stats_history_length: 30,
+            pruning_soft_deadline: None,
+            pruning_progress_log_interval: 1000,
+            pruning_progress_log_period: None,
             utxoindex: false,
             unsafe_rpc: false,
             enable_unsynced_mining: false,
@@ -101,6 +227,65 @@ impl Config {
     pub fn to_builder(&self) -> ConfigBuilder {
         ConfigBuilder { config: self.clone() }
     }
+
+    /// Returns the pruning sanity checks which should actually be performed, taking `enable_sanity_checks` into account
+    pub fn effective_pruning_sanity_checks(&self) -> PruningSanityChecks {
+        if self.enable_sanity_checks {
+            PruningSanityChecks::all()
+        } else {
+            self.pruning_sanity_checks
+        }
+    }
+
+    /// Returns whether the pruning orphan sweep should be performed, taking `pruning_db_writer_threads` into account
+    pub fn effective_pruning_orphan_sweep(&self) -> bool {
+        self.pruning_orphan_sweep || self.pruning_db_writer_threads > 0
+    }
+}
+
+/// A granular selection of the sanity checks performed during pruning. The UTXO commitment check is relatively
+/// cheap, while the rebuild and relation checks are very expensive and are usually only desired for debugging purposes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PruningSanityChecks {
+    /// Verify that the updated pruning point UTXO set matches the UTXO commitment of the pruning point header
+    pub utxo_commitment: bool,
+    /// Rebuild the pruning point proof following pruning and compare it to the proof which was used for pruning
+    pub proof_rebuild: bool,
+    /// Rebuild the pruning point anticone and trusted data following pruning and compare it to the reference data
+    pub trusted_data_rebuild: bool,
+    /// Verify that the retained relations at each level only refer to blocks whose relations at that level were retained.
+    /// Scans all retained relations, hence it is costly as well
+    pub dangling_relations: bool,
+    /// Verify that body-pruned blocks retaining relations are marked header-only and that fully pruned blocks whose headers
+    /// are kept (past pruning points) have no status left
+    pub pruned_statuses: bool,
+    /// Verify, before the traversal deletes any data, that the GHOSTDAG data of blocks retaining level-0 relations only
+    /// refers to retained blocks. Far cheaper than a proof rebuild
+    pub ghostdag_closure: bool,
+}
+
+impl PruningSanityChecks {
+    pub const fn none() -> Self {
+        Self {
+            utxo_commitment: false,
+            proof_rebuild: false,
+            trusted_data_rebuild: false,
+            dangling_relations: false,
+            pruned_statuses: false,
+            ghostdag_closure: false,
+        }
+    }
+
+    pub const fn all() -> Self {
+        Self {
+            utxo_commitment: true,
+            proof_rebuild: true,
+            trusted_data_rebuild: true,
+            dangling_relations: true,
+            pruned_statuses: true,
+            ghostdag_closure: true,
+        }
+    }
 }
 
 impl AsRef<Params> for Config {
@@ -144,6 +329,15 @@ impl ConfigBuilder {
         self
     }
 
+    /// Overrides the pruning and finality depths, e.g., for quickly exercising pruning point movements in tests.
+    /// Panics if the depths are insane relative to merge depth (see [`Params::validate_pruning_depths`])
+    pub fn set_pruning_depths(mut self, pruning_depth: u64, finality_depth: u64) -> Self {
+        self.config.params.pruning_depth = pruning_depth;
+        self.config.params.finality_depth = finality_depth;
+        self.config.params.validate_pruning_depths().unwrap();
+        self
+    }
+
     pub fn apply_args<F>(mut self, edit_func: F) -> Self
     where
         F: Fn(&mut Config),
@@ -162,11 +356,107 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn retain_all_headers(mut self) -> Self {
+        self.config.retain_all_headers = true;
+        self
+    }
+
+    pub fn retain_kept_window_caches(mut self) -> Self {
+        self.config.retain_kept_window_caches = true;
+        self
+    }
+
+    pub fn set_acceptance_data_retention(mut self, acceptance_data_retention: u64) -> Self {
+        self.config.acceptance_data_retention = Some(acceptance_data_retention);
+        self
+    }
+
+    pub fn set_max_pruning_advances_per_cycle(mut self, max_pruning_advances_per_cycle: usize) -> Self {
+        self.config.max_pruning_advances_per_cycle = Some(max_pruning_advances_per_cycle);
+        self
+    }
+
+    pub fn enable_incremental_utxo_commitment_check(mut self) -> Self {
+        self.config.incremental_utxo_commitment_check = true;
+        self
+    }
+
+    pub fn set_pruning_min_blocks_per_commit(mut self, pruning_min_blocks_per_commit: usize) -> Self {
+        self.config.pruning_min_blocks_per_commit = pruning_min_blocks_per_commit;
+        self
+    }
+
+    pub fn set_pruning_max_blocks_per_commit(mut self, pruning_max_blocks_per_commit: usize) -> Self {
+        self.config.pruning_max_blocks_per_commit = Some(pruning_max_blocks_per_commit.max(1));
+        self
+    }
+
+    pub fn set_pruning_utxo_diffs_readahead(mut self, pruning_utxo_diffs_readahead: usize) -> Self {
+        self.config.pruning_utxo_diffs_readahead = pruning_utxo_diffs_readahead;
+        self
+    }
+
+    pub fn disable_pruning_utxo_diffs_precheck(mut self) -> Self {
+        self.config.pruning_utxo_diffs_precheck = false;
+        self
+    }
+
+    pub fn set_pruning_db_write_retries(mut self, retries: u32, backoff: Duration) -> Self {
+        self.config.pruning_db_write_retries = retries;
+        self.config.pruning_db_write_backoff = backoff;
+        self
+    }
+
+    pub fn set_pruning_db_writer_threads(mut self, pruning_db_writer_threads: usize) -> Self {
+        self.config.pruning_db_writer_threads = pruning_db_writer_threads;
+        self
+    }
+
+    pub fn set_pruning_stats_history_length(mut self, pruning_stats_history_length: usize) -> Self {
+        self.config.pruning_stats_history_length = pruning_stats_history_length;
+        self
+    }
+
+    pub fn set_pruning_soft_deadline(mut self, pruning_soft_deadline: Duration) -> Self {
+        self.config.pruning_soft_deadline = Some(pruning_soft_deadline);
+        self
+    }
+
+    pub fn set_pruning_progress_log_interval(mut self, pruning_progress_log_interval: u64) -> Self {
+        self.config.pruning_progress_log_interval = pruning_progress_log_interval;
+        self
+    }
+
+    pub fn set_pruning_progress_log_period(mut self, pruning_progress_log_period: Duration) -> Self {
+        self.config.pruning_progress_log_period = Some(pruning_progress_log_period);
+        self
+    }
+
     pub fn enable_sanity_checks(mut self) -> Self {
         self.config.enable_sanity_checks = true;
         self
     }
 
+    pub fn set_pruning_sanity_checks(mut self, pruning_sanity_checks: PruningSanityChecks) -> Self {
+        self.config.pruning_sanity_checks = pruning_sanity_checks;
+        self
+    }
+
+    pub fn enable_past_pruning_points_verification(mut self) -> Self {
+        self.config.verify_past_pruning_points_on_startup = true;
+        self
+    }
+
+    pub fn enable_pruning_orphan_sweep(mut self) -> Self {
+        self.config.pruning_orphan_sweep = true;
+        self
+    }
+
+    pub fn enable_pruning_post_compaction(mut self) -> Self {
+        self.config.pruning_post_compaction = true;
+        self
+    }
+
     pub fn skip_adding_genesis(mut self) -> Self {
         self.config.process_genesis = false;
         self
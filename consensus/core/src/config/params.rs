@@ -5,6 +5,7 @@ pub use super::{
 };
 use crate::{
     constants::STORAGE_MASS_PARAMETER,
+    errors::config::{ConfigError, ConfigResult},
     network::{NetworkId, NetworkType},
     BlockLevel, KType,
 };
@@ -302,6 +303,26 @@ impl Params {
     pub fn finality_duration(&self) -> u64 {
         self.target_time_per_block * self.finality_depth
     }
+
+    /// Validates the sanity of the pruning and finality depths relative to merge depth. Networks might override these depths
+    /// (e.g., shallow depths for quickly exercising pruning in tests), so we verify that pruning points remain in the finalized
+    /// past: merge depth is bounded by finality depth, and the pruning depth covers both
+    pub fn validate_pruning_depths(&self) -> ConfigResult<()> {
+        if self.finality_depth == 0 {
+            return Err(ConfigError::ZeroFinalityDepth);
+        }
+        if self.finality_depth < self.merge_depth {
+            return Err(ConfigError::FinalityDepthBelowMergeDepth {
+                finality_depth: self.finality_depth,
+                merge_depth: self.merge_depth,
+            });
+        }
+        let min_pruning_depth = self.finality_depth + self.merge_depth;
+        if self.pruning_depth < min_pruning_depth {
+            return Err(ConfigError::PruningDepthTooShallow { pruning_depth: self.pruning_depth, min_pruning_depth });
+        }
+        Ok(())
+    }
 }
 
 impl From<NetworkType> for Params {
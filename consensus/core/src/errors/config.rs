@@ -17,6 +17,18 @@ pub enum ConfigError {
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: --override-pruning-depth and --override-finality-depth can only be used on devnet or simnet")]
+    DepthOverrideOnNonDevnet,
+
+    #[error("Configuration: finality depth must be positive")]
+    ZeroFinalityDepth,
+
+    #[error("Configuration: finality depth {finality_depth} cannot be lower than merge depth {merge_depth}")]
+    FinalityDepthBelowMergeDepth { finality_depth: u64, merge_depth: u64 },
+
+    #[error("Configuration: pruning depth {pruning_depth} cannot be lower than finality depth + merge depth ({min_pruning_depth})")]
+    PruningDepthTooShallow { pruning_depth: u64, min_pruning_depth: u64 },
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
@@ -1,4 +1,4 @@
-use crate::BlockLevel;
+use crate::{blockstatus::BlockStatus, BlockLevel};
 
 use super::{block::RuleError, tx::TxRuleError};
 use kaspa_hashes::Hash;
@@ -65,3 +65,99 @@ pub enum PruningImportError {
 }
 
 pub type PruningImportResult<T> = std::result::Result<T, PruningImportError>;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PruningVerificationError {
+    #[error("rebuilt proof has {1} headers while the reference proof has {0}")]
+    ProofLengthMismatch(usize, usize),
+
+    #[error("proof built following pruning does not match the previous proof at index {index}: built {built}, expected {expected}")]
+    ProofHeaderMismatch { index: usize, expected: Hash, built: Hash },
+
+    #[error("rebuilt pruning point anticone does not match the reference anticone")]
+    AnticoneMismatch,
+
+    #[error("past pruning point at index {0} (or its header) is missing")]
+    MissingPastPruningPoint(u64),
+
+    #[error("the first past pruning point {0} is not genesis")]
+    PastPruningPointsRootMismatch(Hash),
+
+    #[error("past pruning point {current} at index {index} is not in the future of the previous past pruning point {previous}")]
+    BrokenPastPruningPointsChain { index: u64, previous: Hash, current: Hash },
+
+    #[error("the last past pruning point {tip} does not match the current pruning point {pruning_point}")]
+    PastPruningPointsTipMismatch { tip: Hash, pruning_point: Hash },
+
+    #[error("the selected parent {selected_parent} of retained block {block} is neither retained nor ORIGIN")]
+    RetainedSelectedParentMissing { block: Hash, selected_parent: Hash },
+
+    #[error("the mergeset of retained block {block} contains the non-retained block {member}")]
+    RetainedMergesetMemberMissing { block: Hash, member: Hash },
+
+    #[error("retained block {block} has a level {level} relation to {referenced} whose relations at that level were pruned")]
+    DanglingRelation { block: Hash, level: BlockLevel, referenced: Hash },
+
+    #[error("body-pruned block {block} which retains relations has status {status:?} rather than a header-only status")]
+    DemotedBlockStatusMismatch { block: Hash, status: BlockStatus },
+
+    #[error("fully pruned block {block} has status {status:?} left")]
+    PrunedBlockStatusLeft { block: Hash, status: BlockStatus },
+
+    #[error("rebuilt DAA window blocks do not match the reference DAA window blocks")]
+    DaaWindowMismatch,
+
+    #[error("rebuilt GHOSTDAG blocks do not match the reference GHOSTDAG blocks")]
+    GhostdagBlocksMismatch,
+}
+
+pub type PruningVerificationResult<T> = std::result::Result<T, PruningVerificationError>;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PruningRecoveryError {
+    #[error("the UTXO diff of chain block {0} was already pruned, cannot rebuild the pruning point UTXO set")]
+    MissingUtxoDiff(Hash),
+
+    #[error("the rebuilt UTXO set of pruning point {pruning_point} has commitment {actual} while its header commits to {expected}")]
+    UtxoCommitmentMismatch { pruning_point: Hash, expected: Hash, actual: Hash },
+
+    #[error("process exit was initiated while rebuilding the pruning point UTXO set")]
+    Interrupted,
+
+    #[error("the last pruned block {last_pruned} is not below the pruning point {pruning_point}")]
+    LastPrunedNotBelowPruningPoint { last_pruned: Hash, pruning_point: Hash },
+
+    #[error(transparent)]
+    DbWriteFailed(#[from] PruningProcessingError),
+}
+
+pub type PruningRecoveryResult<T> = std::result::Result<T, PruningRecoveryError>;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PruningProcessingError {
+    #[error("writing a pruning batch to the DB failed after {attempts} attempts: {reason}")]
+    DbWriteFailed { attempts: u32, reason: String },
+
+    #[error("the new pruning point {0} is missing its header or GHOSTDAG data")]
+    MissingPruningPointData(Hash),
+
+    #[error("chain block {chain_block} is missing its UTXO diff, so the pruning point UTXO set cannot advance to {pruning_point}")]
+    MissingChainUtxoDiff { chain_block: Hash, pruning_point: Hash },
+
+    #[error("the new pruning point {new} is not a selected chain descendant of the previous pruning point {previous}")]
+    PruningPointNotChainDescendant { previous: Hash, new: Hash },
+
+    #[error("the proof of the new pruning point {pruning_point} is headed by {proof_head:?}")]
+    PruningProofHeadMismatch { pruning_point: Hash, proof_head: Option<Hash> },
+
+    #[error("the proof of the new pruning point {pruning_point} ends at {proof_tail:?} rather than at genesis")]
+    PruningProofTailNotGenesis { pruning_point: Hash, proof_tail: Option<Hash> },
+
+    #[error("the anticone of the new pruning point {pruning_point} is headed by {anticone_head:?}")]
+    AnticoneHeadMismatch { pruning_point: Hash, anticone_head: Option<Hash> },
+
+    #[error("retained GHOSTDAG data is not closed under the kept relations: {0}")]
+    GhostdagClosureViolation(PruningVerificationError),
+}
+
+pub type PruningProcessingResult<T> = std::result::Result<T, PruningProcessingError>;
@@ -8,6 +8,7 @@ use kaspa_muhash::MuHash;
 pub trait MuHashExtensions {
     fn add_transaction(&mut self, tx: &impl VerifiableTransaction, block_daa_score: u64);
     fn add_utxo(&mut self, outpoint: &TransactionOutpoint, entry: &UtxoEntry);
+    fn remove_utxo(&mut self, outpoint: &TransactionOutpoint, entry: &UtxoEntry);
     fn from_transaction(tx: &impl VerifiableTransaction, block_daa_score: u64) -> Self;
     fn from_utxo(outpoint: &TransactionOutpoint, entry: &UtxoEntry) -> Self;
 }
@@ -33,6 +34,12 @@ impl MuHashExtensions for MuHash {
         writer.finalize();
     }
 
+    fn remove_utxo(&mut self, outpoint: &TransactionOutpoint, entry: &UtxoEntry) {
+        let mut writer = self.remove_element_builder();
+        write_utxo(&mut writer, entry, outpoint);
+        writer.finalize();
+    }
+
     fn from_transaction(tx: &impl VerifiableTransaction, block_daa_score: u64) -> Self {
         let mut mh = Self::new();
         mh.add_transaction(tx, block_daa_score);
@@ -21,6 +21,22 @@ pub struct PruningPointTrustedData {
     pub ghostdag_blocks: Vec<TrustedGhostdagData>,
 }
 
+/// A consistent snapshot of the pruning state of consensus
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PruningInfo {
+    /// The current pruning point
+    pub pruning_point: Hash,
+
+    /// The current pruning point candidate
+    pub candidate: Hash,
+
+    /// The index of the pruning point in the past pruning points store (genesis is at index 0)
+    pub index: u64,
+
+    /// The last block visited by an in-progress (or interrupted) data prune. `None` if no data prune is in progress
+    pub last_pruned_point: Option<Hash>,
+}
+
 #[derive(Clone, Copy)]
 pub struct PruningProofMetadata {
     /// The claimed work of the initial relay block (from the prover)
@@ -42,6 +42,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "PruningPointMoved notification: new pruning point {} at index {}", "_0.new_pruning_point", "_0.new_pruning_point_index")]
+    PruningPointMoved(PruningPointMovedNotification),
 }
 }
 
@@ -181,3 +184,17 @@ pub struct PruningPointUtxoSetOverrideNotification {}
 
 #[derive(Debug, Clone)]
 pub struct NewBlockTemplateNotification {}
+
+#[derive(Debug, Clone)]
+pub struct PruningPointMovedNotification {
+    pub old_pruning_point: Hash,
+    pub new_pruning_point: Hash,
+    /// The index of the new pruning point in the past pruning points store
+    pub new_pruning_point_index: u64,
+}
+
+impl PruningPointMovedNotification {
+    pub fn new(old_pruning_point: Hash, new_pruning_point: Hash, new_pruning_point_index: u64) -> Self {
+        Self { old_pruning_point, new_pruning_point, new_pruning_point_index }
+    }
+}
@@ -21,6 +21,7 @@ use crate::{
             headers_selected_tip::HeadersSelectedTipStoreReader,
             past_pruning_points::PastPruningPointsStoreReader,
             pruning::PruningStoreReader,
+            pruning_stats_history::PruningStats,
             relations::RelationsStoreReader,
             statuses::StatusesStoreReader,
             tips::TipsStoreReader,
@@ -32,7 +33,12 @@ use crate::{
         body_processor::BlockBodyProcessor,
         deps_manager::{BlockProcessingMessage, BlockResultSender, BlockTask, VirtualStateProcessingMessage},
         header_processor::HeaderProcessor,
-        pruning_processor::processor::{PruningProcessingMessage, PruningProcessor},
+        pruning_processor::{
+            observer::PruningObserver,
+            processor::{PruningProcessingMessage, PruningProcessor},
+            stats::PruningReport,
+            utxoset_export::{PruningPointUtxoSetChunk, PruningPointUtxoSetCursor},
+        },
         virtual_processor::{errors::PruningImportResult, VirtualStateProcessor},
         ProcessingCounters,
     },
@@ -57,14 +63,14 @@ use kaspa_consensus_core::{
         coinbase::CoinbaseResult,
         consensus::{ConsensusError, ConsensusResult},
         difficulty::DifficultyError,
-        pruning::PruningImportError,
+        pruning::{PruningImportError, PruningProcessingResult, PruningRecoveryResult},
         tx::TxResult,
     },
     header::Header,
     merkle::calc_hash_merkle_root,
     muhash::MuHashExtensions,
     network::NetworkType,
-    pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
+    pruning::{PruningInfo, PruningPointProof, PruningPointTrustedData, PruningPointsList, PruningProofMetadata},
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, Transaction, TransactionOutpoint, UtxoEntry},
     BlockHashSet, BlueWorkType, ChainPath, HashMapCustomHasher,
@@ -73,6 +79,7 @@ use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 
 use crossbeam_channel::{
     bounded as bounded_crossbeam, unbounded as unbounded_crossbeam, Receiver as CrossbeamReceiver, Sender as CrossbeamSender,
+    TrySendError,
 };
 use itertools::Itertools;
 use kaspa_consensusmanager::{SessionLock, SessionReadGuard};
@@ -109,6 +116,7 @@ pub struct Consensus {
 
     // Channels
     block_sender: CrossbeamSender<BlockProcessingMessage>,
+    pruning_sender: CrossbeamSender<PruningProcessingMessage>,
 
     // Processors
     pub(super) header_processor: Arc<HeaderProcessor>,
@@ -139,6 +147,7 @@ pub struct Consensus {
 
     // Signals
     is_consensus_exiting: Arc<AtomicBool>,
+    is_pruning_allowed: Arc<AtomicBool>,
 }
 
 impl Deref for Consensus {
@@ -162,6 +171,7 @@ impl Consensus {
         let params = &config.params;
         let perf_params = &config.perf;
         let is_consensus_exiting: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
+        let is_pruning_allowed: Arc<AtomicBool> = Arc::new(AtomicBool::new(true));
 
         //
         // Storage layer
@@ -252,7 +262,7 @@ impl Consensus {
 
         let virtual_processor = Arc::new(VirtualStateProcessor::new(
             virtual_receiver,
-            pruning_sender,
+            pruning_sender.clone(),
             pruning_receiver.clone(),
             virtual_pool,
             params,
@@ -270,8 +280,10 @@ impl Consensus {
             &storage,
             &services,
             pruning_lock.clone(),
+            notification_root.clone(),
             config.clone(),
             is_consensus_exiting.clone(),
+            is_pruning_allowed.clone(),
         ));
 
         // Ensure the relations stores are initialized
@@ -289,6 +301,7 @@ impl Consensus {
         Self {
             db,
             block_sender: sender,
+            pruning_sender,
             header_processor,
             body_processor,
             virtual_processor,
@@ -301,6 +314,7 @@ impl Consensus {
             config,
             creation_timestamp,
             is_consensus_exiting,
+            is_pruning_allowed,
         }
     }
 
@@ -355,6 +369,41 @@ impl Consensus {
         &self.counters
     }
 
+    /// Returns the next chunk of the pruning point UTXO set. See [`PruningProcessor::export_pruning_point_utxo_set_chunk`]
+    pub fn export_pruning_point_utxo_set_chunk(
+        &self,
+        cursor: Option<PruningPointUtxoSetCursor>,
+        chunk_size: usize,
+    ) -> ConsensusResult<PruningPointUtxoSetChunk> {
+        self.pruning_processor.export_pruning_point_utxo_set_chunk(cursor, chunk_size)
+    }
+
+    /// Rebuilds a corrupted pruning point UTXO set from chain UTXO diffs. See [`PruningProcessor::rebuild_pruning_point_utxo_set`]
+    pub fn rebuild_pruning_point_utxo_set(&self) -> PruningRecoveryResult<()> {
+        self.pruning_processor.rebuild_pruning_point_utxo_set()
+    }
+
+    /// Checks the last pruned marker against the pruning point. See [`PruningProcessor::reconcile_pruning_state`]
+    pub fn reconcile_pruning_state(&self) -> PruningRecoveryResult<()> {
+        self.pruning_processor.reconcile_pruning_state()
+    }
+
+    /// Prunes data in the past of the (already advanced) pruning point `new_pruning_point` and reports the run.
+    /// See [`PruningProcessor::prune_with_report`]
+    pub fn prune_with_report(&self, new_pruning_point: Hash) -> PruningProcessingResult<PruningReport> {
+        self.pruning_processor.prune_with_report(new_pruning_point)
+    }
+
+    /// Returns the statistics of the recent data pruning runs. See [`PruningProcessor::recent_pruning_stats`]
+    pub fn recent_pruning_stats(&self) -> Vec<PruningStats> {
+        self.pruning_processor.recent_pruning_stats()
+    }
+
+    /// Registers an external observer of pruning events. See [`PruningProcessor::set_observer`]
+    pub fn set_pruning_observer(&self, observer: Arc<dyn PruningObserver>) -> Result<(), Arc<dyn PruningObserver>> {
+        self.pruning_processor.set_observer(observer)
+    }
+
     pub fn signal_exit(&self) {
         self.is_consensus_exiting.store(true, Ordering::Relaxed);
         self.block_sender.send(BlockProcessingMessage::Exit).unwrap();
@@ -838,7 +887,7 @@ impl ConsensusApi for Consensus {
     fn get_pruning_point_proof(&self) -> Arc<PruningPointProof> {
         // PRUNE SAFETY: proof is cached before the prune op begins and the
         // pruning point cannot move during the prune so the cache remains valid
-        self.services.pruning_proof_manager.get_pruning_point_proof()
+        self.services.pruning_proof_manager.get_pruning_point_proof_cached()
     }
 
     fn create_virtual_selected_chain_block_locator(&self, low: Option<Hash>, high: Option<Hash>) -> ConsensusResult<Vec<Hash>> {
@@ -974,6 +1023,14 @@ impl ConsensusApi for Consensus {
         self.pruning_point_store.read().pruning_point().unwrap()
     }
 
+    fn get_pruning_info(&self) -> PruningInfo {
+        // All fields are read under a single read guard, hence form a consistent snapshot of the pruning store
+        let pruning_point_read = self.pruning_point_store.read();
+        let (pruning_point, candidate, index) = pruning_point_read.get().unwrap().decompose();
+        let last_pruned_point = pruning_point_read.last_pruned().unwrap_option();
+        PruningInfo { pruning_point, candidate, index, last_pruned_point }
+    }
+
     fn get_daa_window(&self, hash: Hash) -> ConsensusResult<Vec<Hash>> {
         let _guard = self.pruning_lock.blocking_read();
         self.validate_block_exists(hash)?;
@@ -1041,6 +1098,27 @@ impl ConsensusApi for Consensus {
         self.creation_timestamp
     }
 
+    fn set_pruning_allowed(&self, allowed: bool) {
+        self.is_pruning_allowed.store(allowed, Ordering::Relaxed);
+    }
+
+    fn is_pruned(&self, hash: Hash) -> bool {
+        self.pruning_processor.is_pruned(hash)
+    }
+
+    fn prune_to_current_point(&self) -> bool {
+        if self.is_consensus_exiting.load(Ordering::Relaxed) {
+            return false;
+        }
+        self.pruning_processor.request_prune_to_current_point();
+        // Never block the caller: if the channel is full, the processor is already due to process the queued messages and
+        // observes the request then (even if those are drained by the virtual processor, which then sends a message of its own)
+        match self.pruning_sender.try_send(PruningProcessingMessage::PruneToCurrentPoint) {
+            Ok(()) | Err(TrySendError::Full(_)) => true,
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+
     fn finality_point(&self) -> Hash {
         self.virtual_processor.virtual_finality_point(&self.lkg_virtual_state.load().ghostdag_data, self.pruning_point())
     }
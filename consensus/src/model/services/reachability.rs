@@ -21,6 +21,10 @@ pub trait ReachabilityService {
     /// The complexity of this method is `O(log(|future_covering_set(this)|))`
     fn is_dag_ancestor_of(&self, this: Hash, queried: Hash) -> bool;
 
+    /// Checks for each of the blocks in `queried` whether `this` is its DAG ancestor, sharing the reachability data
+    /// of `this` between all checks. See [`Self::is_dag_ancestor_of`] as well.
+    fn is_dag_ancestor_of_many(&self, this: Hash, queried: &[Hash]) -> Vec<bool>;
+
     /// Checks if `this` is DAG ancestor of any of the blocks in `queried`. See [`Self::is_dag_ancestor_of`] as well.
     fn is_dag_ancestor_of_any(&self, this: Hash, queried: &mut impl Iterator<Item = Hash>) -> bool;
 
@@ -54,6 +58,10 @@ impl<T: ReachabilityStoreReader + ?Sized> ReachabilityService for T {
         inquirer::is_dag_ancestor_of(self, this, queried).unwrap()
     }
 
+    fn is_dag_ancestor_of_many(&self, this: Hash, queried: &[Hash]) -> Vec<bool> {
+        inquirer::is_dag_ancestor_of_many(self, this, queried).unwrap()
+    }
+
     fn is_dag_ancestor_of_any(&self, this: Hash, queried: &mut impl Iterator<Item = Hash>) -> bool {
         queried.any(|hash| inquirer::is_dag_ancestor_of(self, this, hash).unwrap())
     }
@@ -121,6 +129,11 @@ impl<T: ReachabilityStoreReader + ?Sized> ReachabilityService for MTReachability
         self.store.read().is_any_dag_ancestor_result(list, queried)
     }
 
+    fn is_dag_ancestor_of_many(&self, this: Hash, queried: &[Hash]) -> Vec<bool> {
+        let read_guard = self.store.read();
+        inquirer::is_dag_ancestor_of_many(read_guard.deref(), this, queried).unwrap()
+    }
+
     fn is_dag_ancestor_of_any(&self, this: Hash, queried: &mut impl Iterator<Item = Hash>) -> bool {
         let read_guard = self.store.read();
         queried.any(|hash| inquirer::is_dag_ancestor_of(read_guard.deref(), this, hash).unwrap())
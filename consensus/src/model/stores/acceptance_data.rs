@@ -52,6 +52,11 @@ impl DbAcceptanceDataStore {
         Self::new(Arc::clone(&self.db), cache_policy)
     }
 
+    /// Manually compacts the store key range, reclaiming the space of deleted entries
+    pub fn compact(&self) {
+        self.access.compact();
+    }
+
     pub fn insert_batch(&self, batch: &mut WriteBatch, hash: Hash, acceptance_data: Arc<AcceptanceData>) -> Result<(), StoreError> {
         if self.access.has(hash)? {
             return Err(StoreError::HashAlreadyExists(hash));
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use kaspa_database::prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, StoreError, StoreResult, DB};
+use kaspa_database::registry::DatabaseStorePrefixes;
+use kaspa_hashes::Hash;
+use rocksdb::WriteBatch;
+
+/// Tracks pruned blocks whose acceptance data was retained (see `Config::acceptance_data_retention`), mapped to their
+/// blue score. This allows deleting the retained data once the blocks fall below the retention depth, by which time
+/// they are no longer reachable by the pruning traversal. Entries bypass the cache since they are rarely read
+#[derive(Clone)]
+pub struct DbAcceptanceRetentionStore {
+    access: CachedDbAccess<Hash, u64>,
+}
+
+impl DbAcceptanceRetentionStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { access: CachedDbAccess::new(db, CachePolicy::Empty, DatabaseStorePrefixes::RetainedAcceptanceData.into()) }
+    }
+
+    pub fn insert_batch(&self, batch: &mut WriteBatch, hash: Hash, blue_score: u64) -> StoreResult<()> {
+        self.access.write(BatchDbWriter::new(batch), hash, blue_score)
+    }
+
+    pub fn delete_batch(&self, batch: &mut WriteBatch, hash: Hash) -> StoreResult<()> {
+        self.access.delete(BatchDbWriter::new(batch), hash)
+    }
+
+    /// Returns all tracked blocks with a blue score lower than `cutoff_blue_score`
+    pub fn expired(&self, cutoff_blue_score: u64) -> StoreResult<Vec<Hash>> {
+        let mut expired = Vec::new();
+        for item in self.access.iterator() {
+            let (key, blue_score) = item.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+            if blue_score < cutoff_blue_score {
+                expired.push(Hash::from_slice(&key));
+            }
+        }
+        Ok(expired)
+    }
+}
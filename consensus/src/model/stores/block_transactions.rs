@@ -57,6 +57,11 @@ impl DbBlockTransactionsStore {
         Self::new(Arc::clone(&self.db), cache_policy)
     }
 
+    /// Manually compacts the store key range, reclaiming the space of deleted entries
+    pub fn compact(&self) {
+        self.access.compact();
+    }
+
     pub fn has(&self, hash: Hash) -> Result<bool, StoreError> {
         self.access.has(hash)
     }
@@ -4,7 +4,7 @@ use kaspa_consensus_core::{blockhash::BlockHashes, BlueWorkType};
 use kaspa_consensus_core::{BlockHashMap, BlockHasher, BlockLevel, HashMapCustomHasher};
 use kaspa_database::prelude::DB;
 use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DbKey};
-use kaspa_database::prelude::{CachePolicy, StoreError};
+use kaspa_database::prelude::{CachePolicy, StoreError, StoreResultExtensions};
 use kaspa_database::registry::{DatabaseStorePrefixes, SEPARATOR};
 use kaspa_hashes::Hash;
 
@@ -12,9 +12,10 @@ use itertools::EitherOrBoth::{Both, Left, Right};
 use itertools::Itertools;
 use kaspa_utils::mem_size::MemSizeEstimator;
 use rocksdb::WriteBatch;
-use serde::{Deserialize, Serialize};
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::iter::once;
-use std::{cell::RefCell, sync::Arc};
+use std::{cell::RefCell, fmt, sync::Arc};
 
 /// Re-export for convenience
 pub use kaspa_consensus_core::{HashKTypeMap, KType};
@@ -29,11 +30,81 @@ pub struct GhostdagData {
     pub blues_anticone_sizes: HashKTypeMap,
 }
 
-#[derive(Clone, Serialize, Deserialize, Copy)]
+#[derive(Clone, Serialize, Copy)]
 pub struct CompactGhostdagData {
     pub blue_score: u64,
     pub blue_work: BlueWorkType,
     pub selected_parent: Hash,
+    /// The block level of the block. Populated at header-processing time and `None` for entries written
+    /// before this field was introduced or for data which was not derived from a full header (e.g., proof blocks)
+    pub block_level: Option<BlockLevel>,
+}
+
+impl<'de> Deserialize<'de> for CompactGhostdagData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CompactGhostdagDataVisitor;
+
+        impl<'de> Visitor<'de> for CompactGhostdagDataVisitor {
+            type Value = CompactGhostdagData;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("struct CompactGhostdagData")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let blue_score = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let blue_work = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                let selected_parent = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(2, &self))?;
+                let block_level = match seq.next_element::<TrailingBlockLevel>()? {
+                    None | Some(TrailingBlockLevel::Missing) => None,
+                    Some(TrailingBlockLevel::Present(block_level)) => block_level,
+                };
+                Ok(CompactGhostdagData { blue_score, blue_work, selected_parent, block_level })
+            }
+        }
+
+        /// The trailing `Option<BlockLevel>` field, read as its bincode encoding (a tag byte optionally followed by the level).
+        /// Entries written by previous versions end right after `selected_parent` and bincode reports such a missing trailing
+        /// field as an EOF error rather than as `None`. Since reading a single byte can only fail on EOF, a failure to read
+        /// the tag is the one error treated as a legacy entry; any other malformed data is propagated as an error
+        enum TrailingBlockLevel {
+            Missing,
+            Present(Option<BlockLevel>),
+        }
+
+        impl<'de> Deserialize<'de> for TrailingBlockLevel {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct TrailingBlockLevelVisitor;
+
+                impl<'de> Visitor<'de> for TrailingBlockLevelVisitor {
+                    type Value = TrailingBlockLevel;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("an optional block level")
+                    }
+
+                    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                        let Ok(Some(tag)) = seq.next_element::<u8>() else {
+                            return Ok(TrailingBlockLevel::Missing);
+                        };
+                        match tag {
+                            0 => Ok(TrailingBlockLevel::Present(None)),
+                            1 => {
+                                let block_level = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                                Ok(TrailingBlockLevel::Present(Some(block_level)))
+                            }
+                            tag => Err(de::Error::invalid_value(de::Unexpected::Unsigned(tag as u64), &"an option tag of 0 or 1")),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_tuple(2, TrailingBlockLevelVisitor)
+            }
+        }
+
+        const FIELDS: &[&str] = &["blue_score", "blue_work", "selected_parent", "block_level"];
+        deserializer.deserialize_struct("CompactGhostdagData", FIELDS, CompactGhostdagDataVisitor)
+    }
 }
 
 impl MemSizeEstimator for GhostdagData {
@@ -50,7 +121,7 @@ impl MemSizeEstimator for CompactGhostdagData {}
 impl From<&GhostdagData> for CompactGhostdagData {
     #[inline(always)]
     fn from(value: &GhostdagData) -> Self {
-        Self { blue_score: value.blue_score, blue_work: value.blue_work, selected_parent: value.selected_parent }
+        Self { blue_score: value.blue_score, blue_work: value.blue_work, selected_parent: value.selected_parent, block_level: None }
     }
 }
 
@@ -196,6 +267,10 @@ impl GhostdagData {
         self.into()
     }
 
+    pub fn to_compact_with_level(&self, block_level: BlockLevel) -> CompactGhostdagData {
+        CompactGhostdagData { block_level: Some(block_level), ..self.into() }
+    }
+
     pub fn add_blue(&mut self, block: Hash, blue_anticone_size: KType, block_blues_anticone_sizes: &BlockHashMap<KType>) {
         // Add the new blue block to mergeset blues
         BlockHashes::make_mut(&mut self.mergeset_blues).push(block);
@@ -296,18 +371,26 @@ impl DbGhostdagStore {
         Self::new(Arc::clone(&self.db), self.level, cache_policy, compact_cache_policy)
     }
 
-    pub fn insert_batch(&self, batch: &mut WriteBatch, hash: Hash, data: &Arc<GhostdagData>) -> Result<(), StoreError> {
+    pub fn insert_batch(
+        &self,
+        batch: &mut WriteBatch,
+        hash: Hash,
+        data: &Arc<GhostdagData>,
+        block_level: BlockLevel,
+    ) -> Result<(), StoreError> {
         if self.access.has(hash)? {
             return Err(StoreError::HashAlreadyExists(hash));
         }
         self.access.write(BatchDbWriter::new(batch), hash, data.clone())?;
-        self.compact_access.write(BatchDbWriter::new(batch), hash, data.to_compact())?;
+        self.compact_access.write(BatchDbWriter::new(batch), hash, data.to_compact_with_level(block_level))?;
         Ok(())
     }
 
+    /// Updates the GHOSTDAG data of an existing block. The block level recorded in the compact data (if any) is preserved
     pub fn update_batch(&self, batch: &mut WriteBatch, hash: Hash, data: &Arc<GhostdagData>) -> Result<(), StoreError> {
+        let block_level = self.compact_access.read(hash).unwrap_option().and_then(|compact| compact.block_level);
         self.access.write(BatchDbWriter::new(batch), hash, data.clone())?;
-        self.compact_access.write(BatchDbWriter::new(batch), hash, data.to_compact())?;
+        self.compact_access.write(BatchDbWriter::new(batch), hash, CompactGhostdagData { block_level, ..data.to_compact() })?;
         Ok(())
     }
 
@@ -365,6 +448,9 @@ impl GhostdagStoreReader for DbGhostdagStore {
 }
 
 impl GhostdagStore for DbGhostdagStore {
+    /// Note that the compact data written here carries no block level, since the level is not derivable from the
+    /// GHOSTDAG data alone. This path is used by the pruning proof build, validation and apply flows; header processing uses
+    /// [`DbGhostdagStore::insert_batch`] which records the level
     fn insert(&self, hash: Hash, data: Arc<GhostdagData>) -> Result<(), StoreError> {
         if self.access.has(hash)? {
             return Err(StoreError::HashAlreadyExists(hash));
@@ -567,4 +653,37 @@ mod tests {
         let expected = BlockHashSet::from_iter([1.into(), 4.into(), 2.into(), 5.into(), 3.into(), 6.into()]);
         assert_eq!(expected, data.unordered_mergeset().collect::<BlockHashSet>());
     }
+
+    #[test]
+    fn test_compact_data_legacy_deserialization() {
+        #[derive(Serialize)]
+        struct LegacyCompactGhostdagData {
+            blue_score: u64,
+            blue_work: BlueWorkType,
+            selected_parent: Hash,
+        }
+
+        let legacy = LegacyCompactGhostdagData { blue_score: 7, blue_work: 11u64.into(), selected_parent: 3.into() };
+        let decoded: CompactGhostdagData = bincode::deserialize(&bincode::serialize(&legacy).unwrap()).unwrap();
+        assert_eq!(decoded.blue_score, 7);
+        assert_eq!(decoded.blue_work, 11u64.into());
+        assert_eq!(decoded.selected_parent, 3.into());
+        assert_eq!(decoded.block_level, None);
+
+        let data = CompactGhostdagData { blue_score: 7, blue_work: 11u64.into(), selected_parent: 3.into(), block_level: Some(2) };
+        let decoded: CompactGhostdagData = bincode::deserialize(&bincode::serialize(&data).unwrap()).unwrap();
+        assert_eq!(decoded.block_level, Some(2));
+
+        let data = CompactGhostdagData { block_level: None, ..data };
+        let decoded: CompactGhostdagData = bincode::deserialize(&bincode::serialize(&data).unwrap()).unwrap();
+        assert_eq!(decoded.block_level, None);
+
+        // Corrupted trailing data must not be mistaken for a legacy entry
+        let mut corrupted = bincode::serialize(&legacy).unwrap();
+        corrupted.push(5);
+        assert!(bincode::deserialize::<CompactGhostdagData>(&corrupted).is_err());
+        let mut truncated = bincode::serialize(&legacy).unwrap();
+        truncated.push(1);
+        assert!(bincode::deserialize::<CompactGhostdagData>(&truncated).is_err());
+    }
 }
@@ -79,6 +79,12 @@ impl DbHeadersStore {
         Self::new(Arc::clone(&self.db), cache_policy, compact_cache_policy)
     }
 
+    /// Manually compacts the key ranges of both header stores, reclaiming the space of deleted entries
+    pub fn compact(&self) {
+        self.headers_access.compact();
+        self.compact_headers_access.compact();
+    }
+
     pub fn has(&self, hash: Hash) -> StoreResult<bool> {
         self.headers_access.has(hash)
     }
@@ -1,4 +1,5 @@
 pub mod acceptance_data;
+pub mod acceptance_retention;
 pub mod block_transactions;
 pub mod block_window_cache;
 pub mod children;
@@ -9,6 +10,8 @@ pub mod headers;
 pub mod headers_selected_tip;
 pub mod past_pruning_points;
 pub mod pruning;
+pub mod pruning_queue;
+pub mod pruning_stats_history;
 pub mod pruning_utxoset;
 pub mod reachability;
 pub mod relations;
@@ -1,16 +1,23 @@
 use std::sync::Arc;
 
-use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
+use itertools::Itertools;
+use kaspa_consensus_core::BlockHashMap;
+use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter, MemoryWriter};
 use kaspa_database::prelude::{CachePolicy, DB};
-use kaspa_database::prelude::{StoreError, StoreResult};
+use kaspa_database::prelude::{StoreError, StoreResult, StoreResultExtensions};
 use kaspa_database::registry::DatabaseStorePrefixes;
 use kaspa_hashes::Hash;
+use parking_lot::RwLock;
 use rocksdb::WriteBatch;
 
 use super::U64Key;
 
 pub trait PastPruningPointsStoreReader {
     fn get(&self, index: u64) -> StoreResult<Hash>;
+    /// Returns whether `hash` is recorded as a past pruning point (at any index)
+    fn contains(&self, hash: Hash) -> StoreResult<bool>;
+    /// Iterates all past pruning points written to the DB as `(index, hash)` pairs in increasing index order, up to the first gap
+    fn iter(&self) -> impl Iterator<Item = (u64, Hash)> + '_;
 }
 
 pub trait PastPruningPointsStore: PastPruningPointsStoreReader {
@@ -24,11 +31,18 @@ pub trait PastPruningPointsStore: PastPruningPointsStoreReader {
 pub struct DbPastPruningPointsStore {
     db: Arc<DB>,
     access: CachedDbAccess<U64Key, Hash>,
+    /// A reverse hash -> index map of all past pruning points. Lazily loaded from the DB on first
+    /// membership query and kept up to date by all subsequent writes
+    reverse_index: Arc<RwLock<Option<BlockHashMap<u64>>>>,
 }
 
 impl DbPastPruningPointsStore {
     pub fn new(db: Arc<DB>, cache_policy: CachePolicy) -> Self {
-        Self { db: Arc::clone(&db), access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::PastPruningPoints.into()) }
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db, cache_policy, DatabaseStorePrefixes::PastPruningPoints.into()),
+            reverse_index: Default::default(),
+        }
     }
 
     pub fn clone_with_new_cache(&self, cache_policy: CachePolicy) -> Self {
@@ -40,14 +54,77 @@ impl DbPastPruningPointsStore {
             return Err(StoreError::KeyAlreadyExists(index.to_string()));
         }
         self.access.write(BatchDbWriter::new(batch), index.into(), pruning_point)?;
+        self.update_reverse_index(index, None, pruning_point);
+        Ok(())
+    }
+
+    /// Reverts the cached effects of [`Self::insert_batch`] for `index`. Used when the batch could not be written to the DB
+    pub fn revert_cached_insert(&self, index: u64, pruning_point: Hash) {
+        self.access.delete(MemoryWriter, index.into()).unwrap();
+        if let Some(reverse_index) = self.reverse_index.write().as_mut() {
+            if reverse_index.get(&pruning_point) == Some(&index) {
+                reverse_index.remove(&pruning_point);
+            }
+        }
+    }
+
+    fn update_reverse_index(&self, index: u64, replaced: Option<Hash>, pruning_point: Hash) {
+        if let Some(reverse_index) = self.reverse_index.write().as_mut() {
+            if let Some(replaced) = replaced {
+                if reverse_index.get(&replaced) == Some(&index) {
+                    reverse_index.remove(&replaced);
+                }
+            }
+            reverse_index.insert(pruning_point, index);
+        }
+    }
+
+    fn load_reverse_index(&self) -> StoreResult<()> {
+        let mut reverse_index = self.reverse_index.write();
+        if reverse_index.is_none() {
+            let mut map = BlockHashMap::new();
+            for item in self.scan() {
+                let (index, pruning_point) = item?;
+                map.insert(pruning_point, index);
+            }
+            *reverse_index = Some(map);
+        }
         Ok(())
     }
+
+    /// Scans all DB entries with a single prefix iteration, in DB key order (which differs from the index order, see `iter`)
+    fn scan(&self) -> impl Iterator<Item = StoreResult<(u64, Hash)>> + '_ {
+        self.access.iterator().map(|item| {
+            let (key, pruning_point) = item.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+            let index = <[u8; 8]>::try_from(key.as_ref())
+                .map(u64::from_le_bytes)
+                .map_err(|_| StoreError::DataInconsistency(format!("unexpected past pruning point key {key:?}")))?;
+            Ok((index, pruning_point))
+        })
+    }
 }
 
 impl PastPruningPointsStoreReader for DbPastPruningPointsStore {
     fn get(&self, index: u64) -> StoreResult<Hash> {
         self.access.read(index.into())
     }
+
+    fn contains(&self, hash: Hash) -> StoreResult<bool> {
+        if let Some(reverse_index) = self.reverse_index.read().as_ref() {
+            return Ok(reverse_index.contains_key(&hash));
+        }
+        self.load_reverse_index()?;
+        Ok(self.reverse_index.read().as_ref().expect("loaded above").contains_key(&hash))
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (u64, Hash)> + '_ {
+        // Keys are little-endian encoded (see `U64Key`), so the DB key order does not match the index order. We hence read the
+        // whole bucket with a single prefix scan (rather than a point lookup per index), order it by index and stop at the
+        // first gap, since indices are contiguous from zero. The history holds one entry per pruning period, so it's small
+        let mut entries = self.scan().map(|item| item.unwrap()).collect_vec();
+        entries.sort_unstable_by_key(|&(index, _)| index);
+        entries.into_iter().zip(0u64..).map_while(|(entry, expected_index)| (entry.0 == expected_index).then_some(entry))
+    }
 }
 
 impl PastPruningPointsStore for DbPastPruningPointsStore {
@@ -59,7 +136,68 @@ impl PastPruningPointsStore for DbPastPruningPointsStore {
     }
 
     fn set(&self, index: u64, pruning_point: Hash) -> StoreResult<()> {
+        let replaced = self.access.read(index.into()).unwrap_option();
         self.access.write(DirectDbWriter::new(&self.db), index.into(), pruning_point)?;
+        self.update_reverse_index(index, replaced, pruning_point);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+
+    #[test]
+    fn test_past_pruning_points_iter() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let store = DbPastPruningPointsStore::new(db.clone(), CachePolicy::Count(16));
+        assert_eq!(store.iter().next(), None);
+
+        // A history long enough for the little-endian key order to diverge from the index order
+        let mut expected: Vec<(u64, Hash)> = (0..600u64).map(|index| (index, Hash::from_u64_word(index + 1000))).collect();
+        let mut batch = WriteBatch::default();
+        for &(index, pruning_point) in expected.iter().take(300) {
+            store.insert_batch(&mut batch, index, pruning_point).unwrap();
+        }
+        db.write(batch).unwrap();
+        for &(index, pruning_point) in expected.iter().skip(300) {
+            store.insert(index, pruning_point).unwrap();
+        }
+        // Overriding existing entries is reflected by the iteration
+        expected[256].1 = Hash::from_u64_word(5000);
+        store.set(256, expected[256].1).unwrap();
+
+        assert_eq!(store.iter().collect::<Vec<_>>(), expected);
+        // A store with a fresh cache reads the same history from the DB
+        assert_eq!(store.clone_with_new_cache(CachePolicy::Empty).iter().collect::<Vec<_>>(), expected);
+        assert_eq!(store.iter().nth(299), Some(expected[299]));
+
+        // Iteration stops at the first gap in the indices
+        store.insert(601, Hash::from_u64_word(6000)).unwrap();
+        assert_eq!(store.iter().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_past_pruning_points_revert_cached_insert() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let store = DbPastPruningPointsStore::new(db.clone(), CachePolicy::Count(16));
+        store.insert(0, Hash::from_u64_word(1)).unwrap();
+        assert!(store.contains(Hash::from_u64_word(1)).unwrap());
+
+        // Stage an insertion whose batch is never written to the DB
+        let mut batch = WriteBatch::default();
+        store.insert_batch(&mut batch, 1, Hash::from_u64_word(2)).unwrap();
+        assert!(store.contains(Hash::from_u64_word(2)).unwrap());
+        drop(batch);
+
+        store.revert_cached_insert(1, Hash::from_u64_word(2));
+        assert!(!store.contains(Hash::from_u64_word(2)).unwrap());
+        assert_eq!(store.iter().collect::<Vec<_>>(), vec![(0, Hash::from_u64_word(1))]);
+        // The insertion can now be retried
+        let mut batch = WriteBatch::default();
+        store.insert_batch(&mut batch, 1, Hash::from_u64_word(2)).unwrap();
+        db.write(batch).unwrap();
+        assert_eq!(store.clone_with_new_cache(CachePolicy::Empty).get(1).unwrap(), Hash::from_u64_word(2));
+    }
+}
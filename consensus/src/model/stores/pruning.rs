@@ -54,6 +54,7 @@ pub struct DbPruningStore {
     db: Arc<DB>,
     access: CachedDbItem<PruningPointInfo>,
     history_root_access: CachedDbItem<Hash>,
+    last_pruned_access: CachedDbItem<Hash>,
 }
 
 impl DbPruningStore {
@@ -61,7 +62,8 @@ impl DbPruningStore {
         Self {
             db: Arc::clone(&db),
             access: CachedDbItem::new(db.clone(), DatabaseStorePrefixes::PruningPoint.into()),
-            history_root_access: CachedDbItem::new(db, DatabaseStorePrefixes::HistoryRoot.into()),
+            history_root_access: CachedDbItem::new(db.clone(), DatabaseStorePrefixes::HistoryRoot.into()),
+            last_pruned_access: CachedDbItem::new(db, DatabaseStorePrefixes::LastPrunedBlock.into()),
         }
     }
 
@@ -76,6 +78,22 @@ impl DbPruningStore {
     pub fn set_history_root(&mut self, batch: &mut WriteBatch, history_root: Hash) -> StoreResult<()> {
         self.history_root_access.write(BatchDbWriter::new(batch), &history_root)
     }
+
+    /// Returns the last block visited by an in-progress (or interrupted) data prune
+    pub fn last_pruned(&self) -> StoreResult<Hash> {
+        self.last_pruned_access.read()
+    }
+
+    /// Records the last block visited by an in-progress data prune. Expected to be written in the same batch as the
+    /// deletions up to this block
+    pub fn set_last_pruned(&mut self, batch: &mut WriteBatch, last_pruned: Hash) -> StoreResult<()> {
+        self.last_pruned_access.write(BatchDbWriter::new(batch), &last_pruned)
+    }
+
+    /// Clears the last pruned marker once a data prune completes
+    pub fn clear_last_pruned(&mut self, batch: &mut WriteBatch) -> StoreResult<()> {
+        self.last_pruned_access.remove(BatchDbWriter::new(batch))
+    }
 }
 
 impl PruningStoreReader for DbPruningStore {
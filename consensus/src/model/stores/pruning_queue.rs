@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use kaspa_database::prelude::{CachePolicy, CachedDbItem, DB};
+use kaspa_database::prelude::{CachedDbAccess, DirectDbWriter, StoreResult};
+use kaspa_database::registry::DatabaseStorePrefixes;
+use kaspa_hashes::Hash;
+use serde::{Deserialize, Serialize};
+
+use super::U64Key;
+
+/// A DB-backed FIFO segment of the pruning traversal queue, keyed by the sequential position of each entry.
+/// Used for bounding the memory of the traversal queue on wide DAGs (see `Config::pruning_queue_spill_threshold`).
+/// Entries bypass the cache since each is written and read exactly once.
+///
+/// The store mostly holds scratch data of the running traversal. The only entries which outlive it are the frontier of a
+/// traversal stopped by its soft deadline (see `Config::pruning_soft_deadline`), which are recorded by a
+/// [`DeferredTraversalFrontier`] and resumed from by the next run for the same pruning point. Any other leftovers (e.g., of a
+/// traversal interrupted by a process exit) are cleared, and the traversal restarts from ORIGIN
+#[derive(Clone)]
+pub struct DbPruningQueueStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<U64Key, Hash>,
+    deferred_frontier_access: CachedDbItem<DeferredTraversalFrontier>,
+}
+
+/// The frontier of a traversal which was stopped by its soft deadline: the queue entries at positions `[start, end)` are the
+/// blocks which remain to be visited by the data prune up to `pruning_point`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeferredTraversalFrontier {
+    pub pruning_point: Hash,
+    pub start: u64,
+    pub end: u64,
+}
+
+impl DbPruningQueueStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::PruningQueue.into()),
+            deferred_frontier_access: CachedDbItem::new(db, DatabaseStorePrefixes::PruningDeferredFrontier.into()),
+        }
+    }
+
+    /// Writes `hashes` at the consecutive positions starting at `first_position`
+    pub fn write_range(&self, first_position: u64, hashes: &[Hash]) -> StoreResult<()> {
+        self.access.write_many_without_cache(
+            DirectDbWriter::new(&self.db),
+            &mut hashes.iter().enumerate().map(|(i, &hash)| ((first_position + i as u64).into(), hash)),
+        )
+    }
+
+    /// Reads and deletes `count` entries at the consecutive positions starting at `first_position`
+    pub fn take_range(&self, first_position: u64, count: usize) -> StoreResult<Vec<Hash>> {
+        let positions = (first_position..first_position + count as u64).map(U64Key::from);
+        let hashes = positions.clone().map(|key| self.access.read(key)).collect::<StoreResult<Vec<_>>>()?;
+        self.access.delete_many(DirectDbWriter::new(&self.db), &mut positions.clone())?;
+        Ok(hashes)
+    }
+
+    /// Deletes all entries (e.g., leftovers of an interrupted traversal), including a deferred frontier record
+    pub fn clear(&mut self) -> StoreResult<()> {
+        self.deferred_frontier_access.remove(DirectDbWriter::new(&self.db))?;
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+
+    /// Returns the record of the frontier left by a traversal which was stopped by its soft deadline
+    pub fn deferred_frontier(&self) -> StoreResult<DeferredTraversalFrontier> {
+        self.deferred_frontier_access.read()
+    }
+
+    /// Records that the entries at the positions `[frontier.start, frontier.end)` are the frontier of a deferred traversal.
+    /// Expected to be written only once all these entries were written
+    pub fn set_deferred_frontier(&mut self, frontier: DeferredTraversalFrontier) -> StoreResult<()> {
+        self.deferred_frontier_access.write(DirectDbWriter::new(&self.db), &frontier)
+    }
+
+    /// Removes the deferred frontier record, leaving its entries to be consumed by the resumed traversal. Expected to be called
+    /// before the entries are consumed, so that a resumed traversal which is interrupted again restarts from ORIGIN
+    pub fn clear_deferred_frontier(&mut self) -> StoreResult<()> {
+        self.deferred_frontier_access.remove(DirectDbWriter::new(&self.db))
+    }
+}
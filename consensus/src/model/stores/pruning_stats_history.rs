@@ -0,0 +1,262 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use kaspa_database::prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, StoreResult, DB};
+use kaspa_database::registry::DatabaseStorePrefixes;
+use kaspa_hashes::Hash;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use rocksdb::WriteBatch;
+use serde::{Deserialize, Serialize};
+
+use super::U64Key;
+
+/// Statistics of a single data pruning run. Durations are accumulated per category over the whole
+/// run, which allows telling which stores dominate a slow prune
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PruningStats {
+    /// The unix time (in milliseconds) at which the run completed
+    pub timestamp: u64,
+    /// The point up to which data was pruned by the previous run (the history root), if known
+    pub previous_pruning_point: Option<Hash>,
+    /// The pruning point up to which data was pruned by this run
+    pub pruning_point: Hash,
+    /// The number of traversed blocks in the antifuture of the pruning point
+    pub traversed: u64,
+    /// The number of fully pruned blocks (including DAG relations)
+    pub pruned: u64,
+    /// The number of collected orphan blocks (see `Config::pruning_orphan_sweep`)
+    pub orphans: u64,
+    /// The number of retained blocks whose selected parent was pruned and hence reassigned to ORIGIN by the GHOSTDAG
+    /// rewrite. Normally these are only the lowest blocks of the retained windows, so an abnormal count hints at an
+    /// unexpected pruning shape
+    pub selected_parent_reassignments: u64,
+    /// Overall time of the traversal, including lock yields and IO throttling
+    pub traversal_duration: Duration,
+    /// Time spent rewriting the GHOSTDAG data of retained blocks
+    pub ghostdag_updates_duration: Duration,
+    /// Time spent staging deletions of block body and UTXO related data
+    pub body_deletes_duration: Duration,
+    /// Time spent staging deletions of header related data (relations, reachability, GHOSTDAG, statuses and headers)
+    pub header_deletes_duration: Duration,
+    /// Time spent committing staged relations and reachability changes into the write batches
+    pub staging_commits_duration: Duration,
+    /// Time spent writing deletion batches to the DB. Batches written in the background (see `Config::pruning_db_writer_threads`)
+    /// only account for the time spent waiting on the writers
+    pub db_writes_duration: Duration,
+    /// Overall time of the run, from preparing the pruning plan up to completion
+    pub total_duration: Duration,
+    /// Time spent obtaining the pruning proof and trusted data and computing the pruning plan
+    pub plan_duration: Duration,
+    /// Time spent pruning body tips and the selected chain index
+    pub tips_and_chain_duration: Duration,
+    /// The number of traversal batches written in the background (see `Config::pruning_db_writer_threads`)
+    pub offloaded_batches: u64,
+}
+
+impl MemSizeEstimator for PruningStats {}
+
+impl PruningStats {
+    /// Returns the number of traversed blocks per second
+    pub fn traversal_throughput(&self) -> f64 {
+        let secs = self.traversal_duration.as_secs_f64();
+        if secs > 0.0 {
+            self.traversed as f64 / secs
+        } else {
+            0.0
+        }
+    }
+}
+
+/// The persisted form of a history entry. Records are versioned so that the layout of [`PruningStats`] can evolve: a layout
+/// change adds a new variant (with older variants converted on read) instead of altering the encoding of existing entries
+#[derive(Clone, Serialize, Deserialize)]
+enum PruningStatsRecord {
+    V1(PruningStatsV1),
+    V2(PruningStats),
+}
+
+impl MemSizeEstimator for PruningStatsRecord {}
+
+impl From<PruningStatsRecord> for PruningStats {
+    fn from(record: PruningStatsRecord) -> Self {
+        match record {
+            PruningStatsRecord::V1(stats) => stats.into(),
+            PruningStatsRecord::V2(stats) => stats,
+        }
+    }
+}
+
+/// The original [`PruningStats`] layout, which predates the plan and tips-and-chain durations and the offloaded batches
+/// count. Must not be modified
+#[derive(Clone, Serialize, Deserialize)]
+struct PruningStatsV1 {
+    timestamp: u64,
+    previous_pruning_point: Option<Hash>,
+    pruning_point: Hash,
+    traversed: u64,
+    pruned: u64,
+    orphans: u64,
+    selected_parent_reassignments: u64,
+    traversal_duration: Duration,
+    ghostdag_updates_duration: Duration,
+    body_deletes_duration: Duration,
+    header_deletes_duration: Duration,
+    staging_commits_duration: Duration,
+    db_writes_duration: Duration,
+    total_duration: Duration,
+}
+
+impl From<PruningStatsV1> for PruningStats {
+    fn from(stats: PruningStatsV1) -> Self {
+        Self {
+            timestamp: stats.timestamp,
+            previous_pruning_point: stats.previous_pruning_point,
+            pruning_point: stats.pruning_point,
+            traversed: stats.traversed,
+            pruned: stats.pruned,
+            orphans: stats.orphans,
+            selected_parent_reassignments: stats.selected_parent_reassignments,
+            traversal_duration: stats.traversal_duration,
+            ghostdag_updates_duration: stats.ghostdag_updates_duration,
+            body_deletes_duration: stats.body_deletes_duration,
+            header_deletes_duration: stats.header_deletes_duration,
+            staging_commits_duration: stats.staging_commits_duration,
+            db_writes_duration: stats.db_writes_duration,
+            total_duration: stats.total_duration,
+            // Not measured by runs which recorded V1 entries
+            plan_duration: Duration::ZERO,
+            tips_and_chain_duration: Duration::ZERO,
+            offloaded_batches: 0,
+        }
+    }
+}
+
+/// A bounded history of the statistics of recent data pruning runs, kept as a ring buffer of sequence-numbered entries
+/// (see `Config::pruning_stats_history_length`). The history is small and rarely read, hence entries bypass the cache.
+///
+/// The history is diagnostic only, so an unreadable history (e.g., entries written by an unknown future version) is
+/// never an error: it reads as empty, and is discarded by the next push
+#[derive(Clone)]
+pub struct DbPruningStatsHistoryStore {
+    access: CachedDbAccess<U64Key, PruningStatsRecord>,
+}
+
+impl DbPruningStatsHistoryStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self { access: CachedDbAccess::new(db, CachePolicy::Empty, DatabaseStorePrefixes::PruningStatsHistory.into()) }
+    }
+
+    /// Returns all recorded entries along with their sequence numbers, in increasing sequence (i.e., chronological) order,
+    /// or `None` if any entry cannot be read
+    fn entries(&self) -> Option<Vec<(u64, PruningStats)>> {
+        let mut entries = Vec::new();
+        for item in self.access.iterator() {
+            let (key, record) = item.ok()?;
+            let sequence = u64::from_le_bytes(key.as_ref().try_into().ok()?);
+            entries.push((sequence, record.into()));
+        }
+        // Keys are little-endian encoded (see `U64Key`), so the DB key order does not match the sequence order
+        entries.sort_unstable_by_key(|&(sequence, _)| sequence);
+        Some(entries)
+    }
+
+    /// Appends `stats` to the history and evicts the oldest entries such that at most `capacity` entries remain.
+    /// An unreadable history is discarded as a whole, and the sequence restarts
+    pub fn push_batch(&self, batch: &mut WriteBatch, stats: PruningStats, capacity: usize) -> StoreResult<()> {
+        let next = match self.entries() {
+            Some(entries) => {
+                let evicted = (entries.len() + 1).saturating_sub(capacity);
+                for &(sequence, _) in entries.iter().take(evicted) {
+                    self.access.delete(BatchDbWriter::new(batch), sequence.into())?;
+                }
+                entries.last().map_or(0, |&(sequence, _)| sequence + 1)
+            }
+            None => {
+                self.access.delete_all(BatchDbWriter::new(batch))?;
+                0
+            }
+        };
+        if capacity > 0 {
+            self.access.write(BatchDbWriter::new(batch), next.into(), PruningStatsRecord::V2(stats))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the recorded statistics, oldest first. An unreadable history reads as empty
+    pub fn get_all(&self) -> Vec<PruningStats> {
+        self.entries().unwrap_or_default().into_iter().map(|(_, stats)| stats).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+
+    #[test]
+    fn test_pruning_stats_history_ring_buffer() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let store = DbPruningStatsHistoryStore::new(db.clone());
+        assert!(store.get_all().is_empty());
+
+        // More than 256 entries, so that the little-endian key order diverges from the sequence order
+        let stats = |pruned: u64| PruningStats { pruned, ..Default::default() };
+        for pruned in 0..300u64 {
+            let mut batch = WriteBatch::default();
+            store.push_batch(&mut batch, stats(pruned), 30).unwrap();
+            db.write(batch).unwrap();
+        }
+        assert_eq!(store.get_all(), (270..300).map(stats).collect::<Vec<_>>());
+
+        // Shrinking the capacity evicts all surplus entries at once
+        let mut batch = WriteBatch::default();
+        store.push_batch(&mut batch, stats(300), 5).unwrap();
+        db.write(batch).unwrap();
+        assert_eq!(store.get_all(), (296..=300).map(stats).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_pruning_stats_history_record_versions() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let store = DbPruningStatsHistoryStore::new(db.clone());
+        let stats = |pruned: u64| PruningStats { pruned, ..Default::default() };
+        for pruned in 0..3u64 {
+            let mut batch = WriteBatch::default();
+            store.push_batch(&mut batch, stats(pruned), 10).unwrap();
+            db.write(batch).unwrap();
+        }
+
+        // Entries of older record versions remain readable
+        let legacy = PruningStatsV1 {
+            timestamp: 7,
+            previous_pruning_point: None,
+            pruning_point: 8.into(),
+            traversed: 9,
+            pruned: 10,
+            orphans: 0,
+            selected_parent_reassignments: 0,
+            traversal_duration: Duration::from_millis(11),
+            ghostdag_updates_duration: Default::default(),
+            body_deletes_duration: Default::default(),
+            header_deletes_duration: Default::default(),
+            staging_commits_duration: Default::default(),
+            db_writes_duration: Default::default(),
+            total_duration: Duration::from_millis(12),
+        };
+        let mut batch = WriteBatch::default();
+        store.access.write(BatchDbWriter::new(&mut batch), 3.into(), PruningStatsRecord::V1(legacy.clone())).unwrap();
+        db.write(batch).unwrap();
+        assert_eq!(store.get_all(), vec![stats(0), stats(1), stats(2), legacy.into()]);
+
+        // Simulate an entry of an unknown record version
+        let key = [store.access.prefix(), 4u64.to_le_bytes().as_slice()].concat();
+        db.put(key, u32::MAX.to_le_bytes()).unwrap();
+        assert!(store.get_all().is_empty());
+
+        // The next push discards the whole history and restarts it
+        let mut batch = WriteBatch::default();
+        store.push_batch(&mut batch, stats(5), 10).unwrap();
+        db.write(batch).unwrap();
+        assert_eq!(store.get_all(), vec![stats(5)]);
+    }
+}
@@ -2,8 +2,8 @@ use std::sync::Arc;
 
 use kaspa_database::prelude::CachePolicy;
 use kaspa_database::prelude::StoreResult;
-use kaspa_database::prelude::DB;
 use kaspa_database::prelude::{BatchDbWriter, CachedDbItem};
+use kaspa_database::prelude::{DbSnapshot, DB};
 use kaspa_database::registry::DatabaseStorePrefixes;
 use kaspa_hashes::Hash;
 use rocksdb::WriteBatch;
@@ -31,7 +31,54 @@ impl PruningUtxosetStores {
         self.utxoset_position_access.read()
     }
 
+    /// Same as [`Self::utxoset_position`] but reads the position as captured by `snapshot`
+    pub fn snapshot_utxoset_position(&self, snapshot: &DbSnapshot<'_>) -> StoreResult<Hash> {
+        self.utxoset_position_access.read_from_snapshot(snapshot)
+    }
+
     pub fn set_utxoset_position(&mut self, batch: &mut WriteBatch, pruning_utxoset_position: Hash) -> StoreResult<()> {
         self.utxoset_position_access.write(BatchDbWriter::new(batch), &pruning_utxoset_position)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{generate_random_outpoint, generate_random_utxo};
+    use kaspa_consensus_core::utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff};
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    #[test]
+    fn test_pruning_utxoset_snapshot_reads() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut stores = PruningUtxosetStores::new(db.clone(), CachePolicy::Count(16));
+        let mut rng = SmallRng::seed_from_u64(42);
+        let initial: UtxoCollection = (0..8).map(|_| (generate_random_outpoint(&mut rng), generate_random_utxo(&mut rng))).collect();
+
+        let mut batch = WriteBatch::default();
+        stores.utxo_set.write_diff_batch(&mut batch, &UtxoDiff::new(initial.clone(), Default::default())).unwrap();
+        stores.set_utxoset_position(&mut batch, Hash::from_u64_word(1)).unwrap();
+        db.write(batch).unwrap();
+        let snapshot = db.snapshot();
+
+        // Advance the set past the snapshot, similarly to a pruning point utxoset movement step
+        let removed: UtxoCollection = initial.iter().take(3).map(|(&outpoint, entry)| (outpoint, entry.clone())).collect();
+        let added: UtxoCollection = (0..2).map(|_| (generate_random_outpoint(&mut rng), generate_random_utxo(&mut rng))).collect();
+        let mut batch = WriteBatch::default();
+        stores.utxo_set.write_diff_batch(&mut batch, &UtxoDiff::new(added, removed)).unwrap();
+        stores.set_utxoset_position(&mut batch, Hash::from_u64_word(2)).unwrap();
+        db.write(batch).unwrap();
+
+        assert_eq!(stores.utxoset_position().unwrap(), Hash::from_u64_word(2));
+        assert_eq!(stores.utxo_set.iterator().count(), 7);
+        // The snapshot keeps reflecting the set along with the position it corresponds to
+        assert_eq!(stores.snapshot_utxoset_position(&snapshot).unwrap(), Hash::from_u64_word(1));
+        let snapshot_utxos: UtxoCollection = stores
+            .utxo_set
+            .snapshot_iterator(&snapshot)
+            .map(|item| item.map(|(outpoint, entry)| (outpoint, (*entry).clone())).unwrap())
+            .collect();
+        assert_eq!(snapshot_utxos, initial);
+    }
+}
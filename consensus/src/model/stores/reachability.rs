@@ -208,6 +208,14 @@ impl DbReachabilityStore {
     pub fn clone_with_new_cache(&self, cache_policy: CachePolicy, sets_cache_policy: CachePolicy) -> Self {
         Self::with_prefix_end(Arc::clone(&self.db), cache_policy, sets_cache_policy, self.prefix_end)
     }
+
+    /// Manually compacts the key ranges of the reachability data and of the children and future covering sets,
+    /// reclaiming the space of deleted entries
+    pub fn compact(&self) {
+        self.access.compact();
+        self.children_access.access.compact();
+        self.fcs_access.access.compact();
+    }
 }
 
 impl ReachabilityStore for DbReachabilityStore {
@@ -2,7 +2,7 @@ use kaspa_consensus_core::{blockstatus::BlockStatus, BlockHasher};
 use kaspa_database::registry::DatabaseStorePrefixes;
 use parking_lot::{RwLock, RwLockWriteGuard};
 use rocksdb::WriteBatch;
-use std::sync::Arc;
+use std::{error::Error, sync::Arc};
 
 use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
 use kaspa_database::prelude::{CachePolicy, DB};
@@ -46,6 +46,11 @@ impl DbStatusesStore {
     pub fn delete_batch(&self, batch: &mut WriteBatch, hash: Hash) -> Result<(), StoreError> {
         self.access.delete(BatchDbWriter::new(batch), hash)
     }
+
+    /// Iterates over all block statuses in the DB (bypassing the cache)
+    pub fn iterator(&self) -> impl Iterator<Item = Result<(Hash, BlockStatus), Box<dyn Error>>> + '_ {
+        self.access.iterator().map(|iter_result| iter_result.map(|(key_bytes, status)| (Hash::from_slice(&key_bytes), status)))
+    }
 }
 
 pub trait StatusesStoreBatchExtensions {
@@ -39,6 +39,15 @@ impl DbUtxoDiffsStore {
         Self::new(Arc::clone(&self.db), cache_policy)
     }
 
+    pub fn has(&self, hash: Hash) -> Result<bool, StoreError> {
+        self.access.has(hash)
+    }
+
+    /// Manually compacts the store key range, reclaiming the space of deleted entries
+    pub fn compact(&self) {
+        self.access.compact();
+    }
+
     pub fn insert_batch(&self, batch: &mut WriteBatch, hash: Hash, utxo_diff: Arc<UtxoDiff>) -> Result<(), StoreError> {
         if self.access.has(hash)? {
             return Err(StoreError::HashAlreadyExists(hash));
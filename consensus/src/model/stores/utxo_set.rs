@@ -6,9 +6,9 @@ use kaspa_consensus_core::{
     },
 };
 use kaspa_database::prelude::StoreResultExtensions;
-use kaspa_database::prelude::DB;
 use kaspa_database::prelude::{BatchDbWriter, CachedDbAccess, DirectDbWriter};
 use kaspa_database::prelude::{CachePolicy, StoreError};
+use kaspa_database::prelude::{DbSnapshot, DB};
 use kaspa_hashes::Hash;
 use rocksdb::WriteBatch;
 use std::{error::Error, fmt::Display, sync::Arc};
@@ -124,6 +124,20 @@ impl DbUtxoSetStore {
         })
     }
 
+    /// Same as [`Self::iterator`] but reads the store as captured by `snapshot`
+    pub fn snapshot_iterator<'a>(
+        &'a self,
+        snapshot: &'a DbSnapshot<'_>,
+    ) -> impl Iterator<Item = Result<(TransactionOutpoint, Arc<UtxoEntry>), Box<dyn Error>>> + 'a {
+        self.access.snapshot_iterator(snapshot).map(|iter_result| match iter_result {
+            Ok((key_bytes, utxo_entry)) => match UtxoKey::try_from(key_bytes.as_ref()) {
+                Ok(utxo_key) => Ok((utxo_key.into(), utxo_entry)),
+                Err(e) => Err(e.into()),
+            },
+            Err(e) => Err(e),
+        })
+    }
+
     /// Clear the store completely in DB and cache
     pub fn clear(&mut self) -> Result<(), StoreError> {
         self.access.delete_all(DirectDbWriter::new(&self.db))
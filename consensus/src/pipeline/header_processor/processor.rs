@@ -365,7 +365,7 @@ impl HeaderProcessor {
         //
         // Append-only stores: these require no lock and hence done first in order to reduce locking time
         //
-        self.ghostdag_store.insert_batch(&mut batch, ctx.hash, ghostdag_data).unwrap();
+        self.ghostdag_store.insert_batch(&mut batch, ctx.hash, ghostdag_data, ctx.block_level).unwrap();
 
         if let Some(window) = ctx.block_window_for_difficulty {
             self.block_window_cache_for_difficulty.insert(ctx.hash, window);
@@ -443,7 +443,7 @@ impl HeaderProcessor {
         let mut batch = WriteBatch::default();
 
         // This data might have been already written when applying the pruning proof.
-        self.ghostdag_store.insert_batch(&mut batch, ctx.hash, ghostdag_data).unwrap_or_exists();
+        self.ghostdag_store.insert_batch(&mut batch, ctx.hash, ghostdag_data, ctx.block_level).unwrap_or_exists();
 
         let mut relations_write = self.relations_stores.write();
         ctx.known_parents.into_iter().enumerate().for_each(|(level, parents_by_level)| {
@@ -0,0 +1,96 @@
+use crossbeam_channel::{bounded, unbounded, Receiver, Sender};
+use kaspa_consensus_core::errors::pruning::{PruningProcessingError, PruningProcessingResult};
+use kaspa_core::warn;
+use kaspa_database::prelude::DB;
+use rocksdb::WriteBatch;
+use std::{
+    sync::Arc,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// Writes `batch` to the DB, retrying failed writes up to `retries` times with an exponential backoff starting at `backoff`
+pub(super) fn write_batch_with_retry(db: &DB, batch: WriteBatch, retries: u32, mut backoff: Duration) -> PruningProcessingResult<()> {
+    let mut attempts = 1;
+    loop {
+        if attempts > retries {
+            // The last attempt consumes the batch itself
+            return db.write(batch).map_err(|err| PruningProcessingError::DbWriteFailed { attempts, reason: err.to_string() });
+        }
+        // Writing consumes the batch, so all but the last attempt write a copy of its data
+        match db.write(WriteBatch::from_data(batch.data())) {
+            Ok(()) => return Ok(()),
+            Err(err) => {
+                warn!("Pruning DB write failed (attempt {}): {} -- retrying in {:?}", attempts, err, backoff);
+                thread::sleep(backoff);
+                backoff = backoff.saturating_mul(2);
+                attempts += 1;
+            }
+        }
+    }
+}
+
+/// A small pool of dedicated threads writing pruning batches in the background, so that the traversal can prepare the next
+/// batch while the previous one is being written (see `Config::pruning_db_writer_threads`).
+///
+/// Submitted batches might be written in any order, hence only batches which do not depend on each other (e.g., deletions
+/// of distinct keys) should be submitted. Callers must [`Self::flush`] before any write which depends on previously submitted
+/// batches. Write errors are reported by the next flush
+pub(super) struct PruningBatchWriter {
+    sender: Option<Sender<WriteBatch>>,
+    results: Receiver<PruningProcessingResult<()>>,
+    pending: usize,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl PruningBatchWriter {
+    pub fn new(db: Arc<DB>, threads: usize, retries: u32, backoff: Duration) -> Self {
+        // Bounding the channel applies backpressure on the traversal, limiting the memory held by pending batches
+        let (sender, receiver) = bounded::<WriteBatch>(threads);
+        let (results_sender, results) = unbounded();
+        let workers = (0..threads)
+            .map(|i| {
+                let (db, receiver, results_sender) = (db.clone(), receiver.clone(), results_sender.clone());
+                thread::Builder::new()
+                    .name(format!("pruning-db-writer-{}", i))
+                    .spawn(move || {
+                        for batch in receiver {
+                            // The receiving end lives as long as the writer itself, which joins the workers on drop
+                            let _ = results_sender.send(write_batch_with_retry(&db, batch, retries, backoff));
+                        }
+                    })
+                    .unwrap()
+            })
+            .collect();
+        Self { sender: Some(sender), results, pending: 0, workers }
+    }
+
+    /// Queues `batch` for writing, blocking if all writers are busy and the queue is full
+    pub fn submit(&mut self, batch: WriteBatch) {
+        self.sender.as_ref().unwrap().send(batch).expect("writer threads are alive as long as the sender");
+        self.pending += 1;
+    }
+
+    /// Waits for all submitted batches to be written. Returns the first write error, if any
+    pub fn flush(&mut self) -> PruningProcessingResult<()> {
+        let mut result = Ok(());
+        while self.pending > 0 {
+            let write_result = self.results.recv().expect("writer threads are alive as long as the sender");
+            self.pending -= 1;
+            if result.is_ok() {
+                result = write_result;
+            }
+        }
+        result
+    }
+}
+
+impl Drop for PruningBatchWriter {
+    fn drop(&mut self) {
+        // Closing the channel lets the workers complete the pending writes and exit
+        drop(self.sender.take());
+        for worker in self.workers.drain(..) {
+            worker.join().unwrap();
+        }
+    }
+}
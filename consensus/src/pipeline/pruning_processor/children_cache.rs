@@ -0,0 +1,259 @@
+use crate::{
+    model::stores::reachability::{ReachabilityStore, ReachabilityStoreReader},
+    processes::reachability::interval::Interval,
+};
+use kaspa_consensus_core::{blockhash::BlockHashes, BlockHashMap};
+use kaspa_database::prelude::StoreError;
+use kaspa_hashes::Hash;
+use std::{cell::Cell, collections::VecDeque};
+
+/// A small bounded LRU cache of reachability tree children used by the pruning traversal.
+///
+/// The traversal reads the children of each visited block in order to extend its queue, and the
+/// subsequent reachability deletion of the same block reads them again (as do deletions of its
+/// descendants, which search it as their tree parent). Routing these reads through this cache saves
+/// repeated store reads. Entries are invalidated on any write touching the children of a block
+/// (deletion, child replacement or appending).
+///
+/// The cache is scoped to a single traversal batch, i.e., it is cleared once the batch changes are committed
+/// (see [`Self::clear`]), so it never outlives the staging stores it reads through.
+///
+/// All operations are amortized O(1): recency is tracked by a queue of (hash, stamp) pairs where only the pair
+/// matching the current stamp of a live entry counts, and stale pairs are skipped on eviction or compacted away
+pub(super) struct ChildrenCache {
+    entries: BlockHashMap<(BlockHashes, u64)>,
+    order: VecDeque<(Hash, u64)>,
+    next_stamp: u64,
+    capacity: usize,
+    hits: Cell<u64>,
+    lookups: Cell<u64>,
+}
+
+impl ChildrenCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: BlockHashMap::with_capacity(capacity),
+            order: VecDeque::with_capacity(capacity),
+            next_stamp: 0,
+            capacity,
+            hits: Default::default(),
+            lookups: Default::default(),
+        }
+    }
+
+    /// Returns the reachability tree children of `hash`, reading them from `store` on a cache miss
+    pub fn get_children(&mut self, store: &(impl ReachabilityStoreReader + ?Sized), hash: Hash) -> Result<BlockHashes, StoreError> {
+        if let Some(children) = self.lookup(hash) {
+            self.touch(hash);
+            return Ok(children);
+        }
+        let children = store.get_children(hash)?;
+        if self.capacity > 0 {
+            if self.entries.len() >= self.capacity {
+                self.evict_least_recent();
+            }
+            self.entries.insert(hash, (children.clone(), 0));
+            self.touch(hash);
+        }
+        Ok(children)
+    }
+
+    fn lookup(&self, hash: Hash) -> Option<BlockHashes> {
+        self.lookups.set(self.lookups.get() + 1);
+        let children = self.entries.get(&hash).map(|(children, _)| children.clone());
+        if children.is_some() {
+            self.hits.set(self.hits.get() + 1);
+        }
+        children
+    }
+
+    /// Marks the (existing) entry of `hash` as the most recently used
+    fn touch(&mut self, hash: Hash) {
+        let stamp = self.next_stamp;
+        self.next_stamp += 1;
+        self.entries.get_mut(&hash).expect("touched entries exist").1 = stamp;
+        self.order.push_back((hash, stamp));
+        // Bound the stale pairs to a constant factor of the live entries, so that compaction is amortized O(1)
+        if self.order.len() > 2 * self.capacity.max(1) {
+            let entries = &self.entries;
+            self.order.retain(|(hash, stamp)| entries.get(hash).is_some_and(|&(_, current)| current == *stamp));
+        }
+    }
+
+    fn evict_least_recent(&mut self) {
+        while let Some((hash, stamp)) = self.order.pop_front() {
+            if self.entries.get(&hash).is_some_and(|&(_, current)| current == stamp) {
+                self.entries.remove(&hash);
+                return;
+            }
+        }
+    }
+
+    pub fn invalidate(&mut self, hash: Hash) {
+        // The recency pair of the entry becomes stale and is skipped on eviction
+        self.entries.remove(&hash);
+    }
+
+    /// Clears all entries. Hit and lookup counters are kept
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    /// Returns the number of children lookups which were served without a store read
+    pub fn hits(&self) -> u64 {
+        self.hits.get()
+    }
+
+    /// Returns the overall number of children lookups
+    pub fn lookups(&self) -> u64 {
+        self.lookups.get()
+    }
+
+    /// Wraps `store` such that children reads go through this cache and writes invalidate it
+    pub fn wrap<'a, S: ReachabilityStore + ?Sized>(&'a mut self, store: &'a mut S) -> ChildrenCachingStore<'a, S> {
+        ChildrenCachingStore { store, cache: self }
+    }
+}
+
+/// A reachability store wrapper which serves children reads from a [`ChildrenCache`] and keeps it consistent with writes
+pub(super) struct ChildrenCachingStore<'a, S: ReachabilityStore + ?Sized> {
+    store: &'a mut S,
+    cache: &'a mut ChildrenCache,
+}
+
+impl<S: ReachabilityStore + ?Sized> ReachabilityStoreReader for ChildrenCachingStore<'_, S> {
+    fn has(&self, hash: Hash) -> Result<bool, StoreError> {
+        self.store.has(hash)
+    }
+
+    fn get_interval(&self, hash: Hash) -> Result<Interval, StoreError> {
+        self.store.get_interval(hash)
+    }
+
+    fn get_parent(&self, hash: Hash) -> Result<Hash, StoreError> {
+        self.store.get_parent(hash)
+    }
+
+    fn get_children(&self, hash: Hash) -> Result<BlockHashes, StoreError> {
+        // Reads through a shared reference cannot populate the cache, so we only serve existing entries here
+        match self.cache.lookup(hash) {
+            Some(children) => Ok(children),
+            None => self.store.get_children(hash),
+        }
+    }
+
+    fn get_future_covering_set(&self, hash: Hash) -> Result<BlockHashes, StoreError> {
+        self.store.get_future_covering_set(hash)
+    }
+
+    fn count(&self) -> Result<usize, StoreError> {
+        self.store.count()
+    }
+}
+
+impl<S: ReachabilityStore + ?Sized> ReachabilityStore for ChildrenCachingStore<'_, S> {
+    fn init(&mut self, origin: Hash, capacity: Interval) -> Result<(), StoreError> {
+        self.cache.clear();
+        self.store.init(origin, capacity)
+    }
+
+    fn insert(&mut self, hash: Hash, parent: Hash, interval: Interval, height: u64) -> Result<(), StoreError> {
+        self.cache.invalidate(hash);
+        self.store.insert(hash, parent, interval, height)
+    }
+
+    fn set_interval(&mut self, hash: Hash, interval: Interval) -> Result<(), StoreError> {
+        self.store.set_interval(hash, interval)
+    }
+
+    fn append_child(&mut self, hash: Hash, child: Hash) -> Result<(), StoreError> {
+        self.cache.invalidate(hash);
+        self.store.append_child(hash, child)
+    }
+
+    fn insert_future_covering_item(&mut self, hash: Hash, fci: Hash, insertion_index: usize) -> Result<(), StoreError> {
+        self.store.insert_future_covering_item(hash, fci, insertion_index)
+    }
+
+    fn set_parent(&mut self, hash: Hash, new_parent: Hash) -> Result<(), StoreError> {
+        self.store.set_parent(hash, new_parent)
+    }
+
+    fn replace_child(
+        &mut self,
+        hash: Hash,
+        replaced_hash: Hash,
+        replaced_index: usize,
+        replace_with: &[Hash],
+    ) -> Result<(), StoreError> {
+        self.cache.invalidate(hash);
+        self.store.replace_child(hash, replaced_hash, replaced_index, replace_with)
+    }
+
+    fn replace_future_covering_item(
+        &mut self,
+        hash: Hash,
+        replaced_hash: Hash,
+        replaced_index: usize,
+        replace_with: &[Hash],
+    ) -> Result<(), StoreError> {
+        self.store.replace_future_covering_item(hash, replaced_hash, replaced_index, replace_with)
+    }
+
+    fn delete(&mut self, hash: Hash) -> Result<(), StoreError> {
+        self.cache.invalidate(hash);
+        self.store.delete(hash)
+    }
+
+    fn get_height(&self, hash: Hash) -> Result<u64, StoreError> {
+        self.store.get_height(hash)
+    }
+
+    fn set_reindex_root(&mut self, root: Hash) -> Result<(), StoreError> {
+        self.store.set_reindex_root(root)
+    }
+
+    fn get_reindex_root(&self) -> Result<Hash, StoreError> {
+        self.store.get_reindex_root()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::stores::reachability::MemoryReachabilityStore;
+
+    #[test]
+    fn test_children_cache_lru_eviction_and_invalidation() {
+        let h = Hash::from_u64_word;
+        let mut store = MemoryReachabilityStore::new();
+        store.init(h(1), Interval::maximal()).unwrap();
+        for i in 2..=5 {
+            store.insert(h(i), h(1), Interval::empty(), 1).unwrap();
+            store.append_child(h(1), h(i)).unwrap();
+        }
+
+        let mut cache = ChildrenCache::new(2);
+        cache.get_children(&store, h(1)).unwrap();
+        cache.get_children(&store, h(2)).unwrap();
+        // Using 1 makes 2 the least recent entry, which is evicted by the insertion of 3
+        cache.get_children(&store, h(1)).unwrap();
+        cache.get_children(&store, h(3)).unwrap();
+        assert_eq!(cache.entries.len(), 2);
+        assert!(cache.entries.contains_key(&h(1)) && cache.entries.contains_key(&h(3)));
+
+        // Writes through the wrapper invalidate the entry, so the next read observes the store
+        cache.wrap(&mut store).append_child(h(3), h(5)).unwrap();
+        assert!(!cache.entries.contains_key(&h(3)));
+        assert_eq!(cache.get_children(&store, h(3)).unwrap().as_slice(), &[h(5)]);
+
+        // Many touches keep the recency queue bounded
+        for _ in 0..100 {
+            cache.get_children(&store, h(1)).unwrap();
+        }
+        assert!(cache.order.len() <= 4);
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!((cache.lookups(), cache.hits()), (105, 101));
+    }
+}
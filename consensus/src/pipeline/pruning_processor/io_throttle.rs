@@ -0,0 +1,45 @@
+use kaspa_consensus_core::config::BytesPerSec;
+use std::time::{Duration, Instant};
+
+/// Tracks the bytes written by the pruning traversal and computes the delays required
+/// for keeping its write throughput under an optional IO budget
+pub(super) struct IoThrottle {
+    budget: Option<BytesPerSec>,
+    start: Instant,
+    checkpoint: Instant,
+    checkpoint_bytes: u64,
+    total_bytes: u64,
+}
+
+impl IoThrottle {
+    pub fn new(budget: Option<BytesPerSec>) -> Self {
+        let now = Instant::now();
+        Self { budget, start: now, checkpoint: now, checkpoint_bytes: 0, total_bytes: 0 }
+    }
+
+    /// Records a flushed write of `bytes` and returns the delay required for bringing the throughput since
+    /// the last checkpoint back under the budget (if any). The caller is expected to sleep for the returned
+    /// duration without holding any locks and then call [`Self::checkpoint`]
+    pub fn record(&mut self, bytes: usize) -> Option<Duration> {
+        self.checkpoint_bytes += bytes as u64;
+        self.total_bytes += bytes as u64;
+        let budget = self.budget.filter(|&budget| budget > 0)?;
+        let expected = Duration::from_secs_f64(self.checkpoint_bytes as f64 / budget as f64);
+        expected.checked_sub(self.checkpoint.elapsed()).filter(|delay| !delay.is_zero())
+    }
+
+    /// Starts a new measurement window
+    pub fn checkpoint(&mut self) {
+        self.checkpoint = Instant::now();
+        self.checkpoint_bytes = 0;
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    /// Returns the average write throughput (in bytes per second) since creation, including any throttling delays
+    pub fn average_throughput(&self) -> f64 {
+        self.total_bytes as f64 / self.start.elapsed().as_secs_f64().max(f64::EPSILON)
+    }
+}
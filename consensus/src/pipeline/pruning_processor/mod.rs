@@ -1 +1,10 @@
+mod batch_writer;
+mod children_cache;
+mod io_throttle;
+pub mod observer;
+pub mod plan;
 pub mod processor;
+pub mod stats;
+pub mod traversal;
+mod traversal_queue;
+pub mod utxoset_export;
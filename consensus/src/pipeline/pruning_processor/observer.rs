@@ -0,0 +1,14 @@
+use kaspa_hashes::Hash;
+
+/// An external observer of pruning events, e.g., an index maintained alongside consensus which must
+/// be kept in sync with the pruning of UTXO and acceptance data. Callbacks are invoked from the pruning
+/// processor thread right after the relevant changes were committed to the DB, hence they should return
+/// quickly and must not call back into consensus in a way which requires the pruning lock
+pub trait PruningObserver: Send + Sync {
+    /// Called once the pruning point UTXO set was advanced from `old_pruning_point` to `new_pruning_point`
+    fn on_utxo_set_moved(&self, old_pruning_point: Hash, new_pruning_point: Hash);
+
+    /// Called with each committed batch of fully pruned blocks (i.e., blocks whose DAG relations were removed as well).
+    /// Blocks which retain their header or relations (e.g., header-only proof blocks) are not reported
+    fn on_blocks_pruned(&self, blocks: &[Hash]);
+}
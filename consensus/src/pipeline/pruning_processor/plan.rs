@@ -0,0 +1,197 @@
+use crate::model::services::reachability::ReachabilityService;
+use kaspa_consensus_core::{
+    pruning::{PruningPointProof, PruningPointTrustedData},
+    BlockHashMap, BlockHashSet, BlockLevel,
+};
+use kaspa_hashes::Hash;
+use std::collections::hash_map::Entry::Vacant;
+
+/// Describes which data is kept when pruning the past of a new pruning point. Building the plan involves no
+/// store mutations, so it can be computed (and tested) separately from the actual data deletion.
+///
+/// Note that headers of past pruning points are kept as well. These are not part of the plan since they
+/// are tested directly against the past pruning points store (see `PastPruningPointsStoreReader::contains`)
+pub struct PruningPlan {
+    /// The pruning point and its anticone, for which full block data is kept
+    pub keep_blocks: BlockHashSet,
+
+    /// Blocks whose relations are kept (pruning point anticone, DAA/GD windows, pruning proof and anticone roots
+    /// parents at higher levels), mapped to the lowest proof level they are affiliated with. Level-x relations
+    /// of a block are kept only if it is affiliated with a level lower or equal to x
+    pub keep_relations: BlockHashMap<BlockLevel>,
+
+    /// Body tips which are not in the future of the new pruning point. By the prunality proof these
+    /// will never be merged by virtual and hence can be safely deleted
+    pub pruned_tips: Vec<Hash>,
+
+    /// Whether headers of all pruned blocks are retained (with no block status), rather than only
+    /// the headers of past pruning points. Body and UTXO related data is pruned either way
+    pub retain_all_headers: bool,
+
+    /// Whether window cache entries of kept blocks are retained, rather than being evicted along with those of pruned blocks
+    pub retain_kept_window_caches: bool,
+
+    /// The blue score from which the acceptance data of pruned blocks is retained, if acceptance data retention is enabled
+    /// (see `Config::acceptance_data_retention`)
+    pub acceptance_data_cutoff: Option<u64>,
+}
+
+impl PruningPlan {
+    /// Builds the pruning plan for `new_pruning_point`.
+    ///
+    /// `roots_parents_at_level` is expected to return the parents at `level` of all pruning point anticone blocks
+    /// whose block level is lower than `level`. These are marked as not-to-be-deleted in order to optimize multi-level
+    /// parent validation (see ParentsManager) by avoiding the deletion of high-level parents which might still be
+    /// needed for future header validation (avoiding the need for reference blocks; see therein).
+    ///
+    /// Notes:
+    ///
+    /// 1. Normally, such blocks would be part of the proof for this level, but here we address the rare case
+    ///    where there are a few such parallel blocks (since the proof only contains the past of the pruning point's
+    ///    selected-tip-at-level)
+    /// 2. We refer to the pp anticone as roots even though technically it might contain blocks which are not a pure
+    ///    antichain (i.e., some of them are in the past of others). These blocks only add redundant info which would
+    ///    be included anyway.
+    pub fn new(
+        new_pruning_point: Hash,
+        proof: &PruningPointProof,
+        data: &PruningPointTrustedData,
+        roots_parents_at_level: impl Fn(BlockLevel) -> Vec<Hash>,
+        tips: impl IntoIterator<Item = Hash>,
+        reachability: &(impl ReachabilityService + ?Sized),
+        retain_all_headers: bool,
+        retain_kept_window_caches: bool,
+        acceptance_data_cutoff: Option<u64>,
+    ) -> Self {
+        // We keep full data for pruning point and its anticone, relations for DAA/GD
+        // windows and pruning proof, and only headers for past pruning points
+        let keep_blocks: BlockHashSet = data.anticone.iter().copied().collect();
+        let mut keep_relations: BlockHashMap<BlockLevel> = std::iter::empty()
+            .chain(data.anticone.iter().copied())
+            .chain(data.daa_window_blocks.iter().map(|th| th.header.hash))
+            .chain(data.ghostdag_blocks.iter().map(|gd| gd.hash))
+            .chain(proof[0].iter().map(|h| h.hash))
+            .map(|h| (h, 0)) // Mark block level 0 for all the above. Note that below we add the remaining levels
+            .collect();
+
+        for (level, level_proof) in proof.iter().enumerate().skip(1) {
+            let level = level as BlockLevel;
+            for hash in level_proof.iter().map(|header| header.hash).chain(roots_parents_at_level(level)) {
+                if let Vacant(e) = keep_relations.entry(hash) {
+                    // This hash was not added by any lower level -- mark it as affiliated with proof level `level`
+                    e.insert(level);
+                }
+            }
+        }
+
+        // A single batched query, sharing the reachability data of the pruning point between all tips
+        let tips: Vec<Hash> = tips.into_iter().collect();
+        let pruned_tips = tips
+            .iter()
+            .zip(reachability.is_dag_ancestor_of_many(new_pruning_point, &tips))
+            .filter_map(|(&tip, is_in_future)| (!is_in_future).then_some(tip))
+            .collect();
+
+        Self { keep_blocks, keep_relations, pruned_tips, retain_all_headers, retain_kept_window_caches, acceptance_data_cutoff }
+    }
+
+    /// Returns whether level-0 relations of `hash` are kept, which is also the filtering criterion for primary GHOSTDAG data
+    pub fn keeps_level_zero_relations(&self, hash: Hash) -> bool {
+        self.keep_relations.get(&hash) == Some(&0)
+    }
+}
+
+/// Returns the virtual parents which are not in the future of `new_pruning_point` (or equal to it). Pruning the past of the
+/// pruning point is only safe if this set is empty, since otherwise data which virtual still depends on would be deleted
+pub fn virtual_parents_outside_future(
+    new_pruning_point: Hash,
+    virtual_parents: &[Hash],
+    reachability: &(impl ReachabilityService + ?Sized),
+) -> Vec<Hash> {
+    virtual_parents
+        .iter()
+        .zip(reachability.is_dag_ancestor_of_many(new_pruning_point, virtual_parents))
+        .filter_map(|(&parent, is_in_future)| (!is_in_future).then_some(parent))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::stores::reachability::MemoryReachabilityStore,
+        processes::reachability::tests::{StoreValidationExtensions, TreeBuilder},
+    };
+    use kaspa_consensus_core::{blockhash::ORIGIN, header::Header};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_pruning_plan_keep_sets() {
+        let hashes: Vec<Hash> = (0..=11u64).map(Hash::from_u64_word).collect();
+        let h = |i: usize| hashes[i];
+
+        // Build a simple chain 1 <- 2 <- ... <- 10 with a side tip 11 forking from 4
+        let mut store = MemoryReachabilityStore::new();
+        let mut builder = TreeBuilder::new(&mut store);
+        builder.init();
+        builder.add_block(h(1), ORIGIN);
+        for i in 2..=10 {
+            builder.add_block(h(i), h(i - 1));
+        }
+        builder.add_block(h(11), h(4));
+        store.validate_intervals(ORIGIN).unwrap();
+
+        let header = |i: usize| Arc::new(Header::from_precomputed_hash(h(i), vec![]));
+        let new_pruning_point = h(6);
+        // Level 0 proof ends at the pruning point, level 1 proof contains a block which also appears in level 0
+        let proof: PruningPointProof = vec![vec![header(1), header(5), header(6)], vec![header(1), header(3)]];
+        let data = PruningPointTrustedData { anticone: vec![new_pruning_point], daa_window_blocks: vec![], ghostdag_blocks: vec![] };
+
+        let plan = PruningPlan::new(
+            new_pruning_point,
+            &proof,
+            &data,
+            |level| if level == 1 { vec![h(2)] } else { vec![] },
+            [h(10), h(11)],
+            &store,
+            false,
+            false,
+            None,
+        );
+
+        assert_eq!(plan.keep_blocks, BlockHashSet::from_iter([new_pruning_point]));
+        let expected_relations: BlockHashMap<BlockLevel> =
+            [(h(1), 0), (h(5), 0), (h(6), 0), (h(3), 1), (h(2), 1)].into_iter().collect();
+        assert_eq!(plan.keep_relations, expected_relations);
+        assert!(plan.keeps_level_zero_relations(h(1)));
+        assert!(!plan.keeps_level_zero_relations(h(3)));
+        // Only the side tip is outside the future of the pruning point
+        assert_eq!(plan.pruned_tips, vec![h(11)]);
+    }
+
+    #[test]
+    fn test_virtual_parents_outside_future() {
+        let hashes: Vec<Hash> = (0..=8u64).map(Hash::from_u64_word).collect();
+        let h = |i: usize| hashes[i];
+
+        // A chain 1 <- 2 <- ... <- 6 with a side block 7 forking from 4 and a side block 8 forking from 5
+        let mut store = MemoryReachabilityStore::new();
+        let mut builder = TreeBuilder::new(&mut store);
+        builder.init();
+        builder.add_block(h(1), ORIGIN);
+        for i in 2..=6 {
+            builder.add_block(h(i), h(i - 1));
+        }
+        builder.add_block(h(7), h(4));
+        builder.add_block(h(8), h(5));
+        store.validate_intervals(ORIGIN).unwrap();
+
+        let new_pruning_point = h(5);
+        // Virtual parents in the future of the pruning point (or equal to it) are safe
+        assert!(virtual_parents_outside_future(new_pruning_point, &[h(6), h(8)], &store).is_empty());
+        assert!(virtual_parents_outside_future(new_pruning_point, &[new_pruning_point], &store).is_empty());
+        // A virtual parent just below the pruning point, or on a side branch forking below it, means the prune must be skipped
+        assert_eq!(virtual_parents_outside_future(new_pruning_point, &[h(6), h(4)], &store), vec![h(4)]);
+        assert_eq!(virtual_parents_outside_future(new_pruning_point, &[h(7), h(8)], &store), vec![h(7)]);
+    }
+}
@@ -1,5 +1,15 @@
 //! TODO: module comment about locking safety and consistency of various pruning stores
 
+use super::{
+    batch_writer::{write_batch_with_retry, PruningBatchWriter},
+    children_cache::ChildrenCache,
+    io_throttle::IoThrottle,
+    observer::PruningObserver,
+    plan::{virtual_parents_outside_future, PruningPlan},
+    stats::PruningReport,
+    traversal::{visit_batch, PruningTraversalStores, StagedPruningStores, TraversalOutcome},
+    traversal_queue::TraversalQueue,
+};
 use crate::{
     consensus::{
         services::{ConsensusServices, DbParentsManager, DbPruningPointManager},
@@ -11,16 +21,21 @@ use crate::{
             ghostdag::{CompactGhostdagData, GhostdagStoreReader},
             headers::HeaderStoreReader,
             past_pruning_points::PastPruningPointsStoreReader,
-            pruning::{PruningStore, PruningStoreReader},
+            pruning::{PruningPointInfo, PruningStore, PruningStoreReader},
+            pruning_queue::{DbPruningQueueStore, DeferredTraversalFrontier},
+            pruning_stats_history::PruningStats,
+            pruning_utxoset::PruningUtxosetStores,
             reachability::{DbReachabilityStore, ReachabilityStoreReader, StagingReachabilityStore},
-            relations::StagingRelationsStore,
+            relations::{RelationsStoreReader, StagingRelationsStore},
             selected_chain::SelectedChainStore,
             statuses::StatusesStoreReader,
             tips::{TipsStore, TipsStoreReader},
             utxo_diffs::UtxoDiffsStoreReader,
+            utxo_multisets::UtxoMultisetsStoreReader,
+            utxo_set::UtxoSetStoreReader,
         },
     },
-    processes::{pruning_proof::PruningProofManager, reachability::inquirer as reachability, relations},
+    processes::{pruning_proof::PruningProofManager, reachability::inquirer as reachability},
 };
 use crossbeam_channel::Receiver as CrossbeamReceiver;
 use itertools::Itertools;
@@ -28,32 +43,66 @@ use kaspa_consensus_core::{
     blockhash::ORIGIN,
     blockstatus::BlockStatus::StatusHeaderOnly,
     config::Config,
+    errors::{
+        consensus::ConsensusError,
+        pruning::{
+            PruningProcessingError, PruningProcessingResult, PruningRecoveryError, PruningRecoveryResult, PruningVerificationError,
+            PruningVerificationResult,
+        },
+    },
     muhash::MuHashExtensions,
     pruning::{PruningPointProof, PruningPointTrustedData},
     trusted::ExternalGhostdagData,
+    utxo::utxo_diff::{ImmutableUtxoDiff, UtxoDiff},
     BlockHashMap, BlockHashSet, BlockLevel,
 };
+use kaspa_consensus_notify::{
+    notification::{Notification, PruningPointMovedNotification},
+    root::ConsensusNotificationRoot,
+};
 use kaspa_consensusmanager::SessionLock;
-use kaspa_core::{debug, info, warn};
-use kaspa_database::prelude::{BatchDbWriter, MemoryWriter, StoreResultExtensions, DB};
+use kaspa_core::{debug, info, time::unix_now, warn};
+use kaspa_database::prelude::{BatchDbWriter, CachePolicy, StoreResultExtensions, DB};
 use kaspa_hashes::Hash;
 use kaspa_muhash::MuHash;
+use kaspa_notify::notifier::Notify;
 use kaspa_utils::iter::IterExtensions;
 use parking_lot::RwLockUpgradableReadGuard;
+use rayon::prelude::*;
 use rocksdb::WriteBatch;
 use std::{
-    collections::{hash_map::Entry::Vacant, VecDeque},
     ops::Deref,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, OnceLock,
     },
     time::{Duration, Instant},
 };
+use tokio::sync::mpsc::Receiver as TokioReceiver;
+use tracing::{event, field, info_span, Level};
+
+/// The number of UTXO entries folded into the pruning point UTXO multiset per UTXO set lock acquisition
+const UTXO_MULTISET_CHUNK_SIZE: usize = 10_000;
+
+/// The maximal number of reachability children lists cached by the pruning traversal
+const PRUNING_CHILDREN_CACHE_SIZE: usize = 1024;
 
 pub enum PruningProcessingMessage {
     Exit,
-    Process { sink_ghostdag_data: CompactGhostdagData },
+    Process {
+        sink_ghostdag_data: CompactGhostdagData,
+    },
+    /// Wakes the processor for handling a pending prune to the current pruning point (see
+    /// [`PruningProcessor::request_prune_to_current_point`]). The request itself is recorded by a flag rather than by this
+    /// message, so it is never lost if the message is dropped (e.g., drained by the virtual processor) or not sent at all
+    /// due to a full channel: the flag is checked on every processing round
+    PruneToCurrentPoint,
+}
+
+/// The work gathered from a sequence of coalesced processing messages
+#[derive(Default)]
+struct PendingWork {
+    sink_ghostdag_data: Option<CompactGhostdagData>,
 }
 
 /// A processor dedicated for moving the pruning point and pruning any possible data in its past
@@ -76,11 +125,26 @@ pub struct PruningProcessor {
     // Pruning lock
     pruning_lock: SessionLock,
 
+    // Notifier
+    notification_root: Arc<ConsensusNotificationRoot>,
+
     // Config
     config: Arc<Config>,
 
     // Signals
     is_consensus_exiting: Arc<AtomicBool>,
+    /// Gate controlled by the node (closed during IBD). While closed, pruning point movements still
+    /// take place but the deletion of data in their past is deferred until the gate reopens
+    is_pruning_allowed: Arc<AtomicBool>,
+    /// Indicates that a data prune was deferred while the pruning gate was closed, or that it failed and is to be retried
+    has_deferred_prune: AtomicBool,
+    /// Indicates that a prune to the current pruning point was requested and is yet to be processed
+    is_prune_to_current_point_requested: AtomicBool,
+    /// Indicates that the archival mode warning was already emitted, so that it is logged once per process rather than per movement
+    archival_warning_emitted: AtomicBool,
+
+    // An optional external observer of pruning events
+    observer: OnceLock<Arc<dyn PruningObserver>>,
 }
 
 impl Deref for PruningProcessor {
@@ -98,8 +162,10 @@ impl PruningProcessor {
         storage: &Arc<ConsensusStorage>,
         services: &Arc<ConsensusServices>,
         pruning_lock: SessionLock,
+        notification_root: Arc<ConsensusNotificationRoot>,
         config: Arc<Config>,
         is_consensus_exiting: Arc<AtomicBool>,
+        is_pruning_allowed: Arc<AtomicBool>,
     ) -> Self {
         Self {
             receiver,
@@ -110,27 +176,251 @@ impl PruningProcessor {
             pruning_proof_manager: services.pruning_proof_manager.clone(),
             parents_manager: services.parents_manager.clone(),
             pruning_lock,
+            notification_root,
             config,
             is_consensus_exiting,
+            is_pruning_allowed,
+            has_deferred_prune: AtomicBool::new(false),
+            is_prune_to_current_point_requested: AtomicBool::new(false),
+            archival_warning_emitted: AtomicBool::new(false),
+            observer: OnceLock::new(),
         }
     }
 
+    /// Registers an external observer of pruning events. Only a single observer can be registered,
+    /// hence the observer is returned back as an error if one is already set
+    pub fn set_observer(&self, observer: Arc<dyn PruningObserver>) -> Result<(), Arc<dyn PruningObserver>> {
+        self.observer.set(observer)
+    }
+
     pub fn worker(self: &Arc<Self>) {
-        let Ok(PruningProcessingMessage::Process { sink_ghostdag_data }) = self.receiver.recv() else {
+        self.reconcile_pruning_state_or_panic();
+        let mut is_first = true;
+        while let Ok(message) = self.receiver.recv() {
+            let Some(work) = Self::coalesce_messages(message, || self.receiver.try_recv().ok()) else {
+                return;
+            };
+            self.process_pending_work(work, is_first);
+            is_first = false;
+        }
+    }
+
+    /// An async variant of [`Self::worker`] driven by a tokio channel, for embedding the processor within an async service.
+    /// Processing is offloaded to the blocking thread pool, where the pruning lock is acquired and periodically yielded
+    /// exactly as by the threaded worker. Note that the crossbeam receiver given to [`Self::new`] is unused in this mode
+    pub async fn worker_async(self: Arc<Self>, mut receiver: TokioReceiver<PruningProcessingMessage>) {
+        self.reconcile_pruning_state_or_panic();
+        let mut is_first = true;
+        while let Some(message) = receiver.recv().await {
+            let Some(work) = Self::coalesce_messages(message, || receiver.try_recv().ok()) else {
+                return;
+            };
+            let processor = self.clone();
+            tokio::task::spawn_blocking(move || processor.process_pending_work(work, is_first)).await.unwrap();
+            is_first = false;
+        }
+    }
+
+    /// Drains any additional queued messages following `first` (as returned by `try_next`), coalescing them into the most
+    /// recent sink ghostdag data (earlier ones can only point at already surpassed pruning points). Returns `None` if an
+    /// `Exit` message was received (also while draining)
+    fn coalesce_messages(
+        first: PruningProcessingMessage,
+        mut try_next: impl FnMut() -> Option<PruningProcessingMessage>,
+    ) -> Option<PendingWork> {
+        let mut work = PendingWork::default();
+        let mut next = Some(first);
+        // Stops once empty or disconnected: we process what we have, a disconnection will be observed by the next blocking recv
+        while let Some(message) = next {
+            match message {
+                PruningProcessingMessage::Process { sink_ghostdag_data } => work.sink_ghostdag_data = Some(sink_ghostdag_data),
+                // A wake-up only, the request is handled by `process_pending_work`
+                PruningProcessingMessage::PruneToCurrentPoint => {}
+                PruningProcessingMessage::Exit => return None,
+            }
+            next = try_next();
+        }
+        Some(work)
+    }
+
+    /// The processing core shared by both worker variants. This is a long blocking operation
+    fn process_pending_work(&self, work: PendingWork, is_first: bool) {
+        if is_first {
+            // On start-up, check if any pruning workflows require recovery. We wait for the first processing message to arrive
+            // in order to make sure the node is already connected and receiving blocks before we start background recovery operations
+            self.recover_pruning_workflows_if_needed();
+        }
+        if let Some(sink_ghostdag_data) = work.sink_ghostdag_data {
+            self.process_sink_update(sink_ghostdag_data);
+        }
+        if self.is_prune_to_current_point_requested.swap(false, Ordering::Relaxed) {
+            self.prune_to_current_point();
+        }
+    }
+
+    fn process_sink_update(&self, sink_ghostdag_data: CompactGhostdagData) {
+        if let Err(err) = self.advance_pruning_point_and_candidate_if_possible(sink_ghostdag_data) {
+            warn!("Pruning point movement failed: {} -- will retry with the next processing message", err);
             return;
+        }
+        if let Err(err) = self.prune_deferred_if_allowed() {
+            warn!("Deferred data prune failed: {} -- will retry with the next processing message", err);
+        }
+    }
+
+    /// Records a request for pruning the past of the current pruning point without advancing it, e.g., for deleting data which
+    /// became prunable following a configuration change while no pruning point movement occurs. The request is processed by
+    /// the next processing round, hence the caller is expected to wake the worker (see [`PruningProcessingMessage`]). Any
+    /// number of requests made before it is processed are coalesced into a single prune
+    pub fn request_prune_to_current_point(&self) {
+        self.is_prune_to_current_point_requested.store(true, Ordering::Relaxed);
+    }
+
+    /// Prunes data in the past of the current pruning point without advancing it (see [`Self::request_prune_to_current_point`]).
+    /// The keep sets are computed exactly as by a regular prune, and the prune is deferred as usual if the pruning gate is
+    /// closed. Idempotent once all prunable data is gone
+    fn prune_to_current_point(&self) {
+        let pruning_point = self.pruning_point_store.read().pruning_point().unwrap();
+        info!("Processing an explicit data prune up to the current pruning point {}", pruning_point);
+        if let Err(err) = self.prune_or_defer(pruning_point) {
+            warn!("Explicit data prune failed: {} -- will retry with the next processing message", err);
+        }
+    }
+
+    /// Prunes data in the past of the pruning point if a prune was deferred while the pruning gate was closed.
+    /// Since the pruning point store always holds the latest pruning point, any number of movements which occurred
+    /// while the gate was closed are coalesced into a single prune
+    fn prune_deferred_if_allowed(&self) -> PruningProcessingResult<()> {
+        if !self.is_pruning_allowed.load(Ordering::Relaxed) || !self.has_deferred_prune.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+        let pruning_point = self.pruning_point_store.read().pruning_point().unwrap();
+        info!("Processing the deferred data prune up to pruning point {}", pruning_point);
+        self.prune_or_requeue(pruning_point)
+    }
+
+    /// Prunes data in the past of `new_pruning_point` or defers the operation if the pruning gate is currently closed
+    fn prune_or_defer(&self, new_pruning_point: Hash) -> PruningProcessingResult<()> {
+        if self.is_pruning_allowed.load(Ordering::Relaxed) {
+            self.prune_or_requeue(new_pruning_point)
+        } else {
+            debug!("[PRUNING PROCESSOR] pruning is currently not allowed, deferring data prune up to {}", new_pruning_point);
+            self.has_deferred_prune.store(true, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    /// Prunes data in the past of `new_pruning_point`. A failed prune is marked as deferred so that it is retried by the next
+    /// processing message. Since the history root is only updated once a prune completes, a retry (or the recovery workflow
+    /// on restart) covers all data which was not yet pruned
+    fn prune_or_requeue(&self, new_pruning_point: Hash) -> PruningProcessingResult<()> {
+        self.prune(new_pruning_point, self.config.genesis.hash).inspect_err(|_| self.has_deferred_prune.store(true, Ordering::Relaxed))
+    }
+
+    /// Writes `batch` to the DB, retrying a failed write up to `Config::pruning_db_write_retries` times with an exponential
+    /// backoff starting at `Config::pruning_db_write_backoff`.
+    ///
+    /// Note that store caches are updated when the batch is staged, so on final failure they are ahead of the DB. Hence, this
+    /// is only used for writes whose cached changes are either reverted explicitly on failure (the pruning point movement)
+    /// or are recomputed and rewritten by the retried prune (e.g., the history root). Writes whose changes a retry would skip
+    /// since it reads them from the already updated caches (staging commits, the GHOSTDAG rewrite, pruned tips and selected
+    /// chain entries, expired acceptance data) must use [`Self::write_committed_batch_or_panic`] instead
+    fn write_batch_with_retry(&self, batch: WriteBatch) -> PruningProcessingResult<()> {
+        write_batch_with_retry(&self.db, batch, self.config.pruning_db_write_retries, self.config.pruning_db_write_backoff)
+    }
+
+    /// Writes `batch` with retries as [`Self::write_batch_with_retry`], but panics on final failure. Used for batches whose
+    /// changes were already applied to store caches which cannot be reverted, e.g., by committing the staging reachability
+    /// and relations stores (or by applying a UTXO diff to the pruning point UTXO set). Proceeding with such caches ahead of
+    /// the DB would corrupt consensus, whereas on restart the caches are reloaded from the DB and the recovery workflow
+    /// resumes the interrupted work
+    fn write_committed_batch_or_panic(&self, batch: WriteBatch) {
+        if let Err(err) = self.write_batch_with_retry(batch) {
+            panic!("Header and Block pruning: {}; store caches are ahead of the DB, the node must be restarted", err);
+        }
+    }
+
+    /// Waits for the pending writes of `batch_writer` (if any), panicking on failure (see [`Self::write_committed_batch_or_panic`])
+    fn flush_batch_writer_or_panic(batch_writer: Option<&mut PruningBatchWriter>) {
+        if let Some(Err(err)) = batch_writer.map(|batch_writer| batch_writer.flush()) {
+            panic!("Header and Block pruning: {}; store caches are ahead of the DB, the node must be restarted", err);
+        }
+    }
+
+    fn reconcile_pruning_state_or_panic(&self) {
+        if let Err(err) = self.reconcile_pruning_state() {
+            panic!("The pruning state is inconsistent ({}); the node must be resynced", err);
+        }
+    }
+
+    /// Checks the last pruned marker against the pruning point. A marker below the pruning point indicates an interrupted
+    /// data prune, which is reported here and resumed by [`Self::recover_pruning_workflows_if_needed`] once block processing
+    /// starts. This includes an interrupted re-prune up to the current pruning point (see [`Self::prune_to_current_point`]),
+    /// which sets the marker while the history root already equals the pruning point. A marker which is not below the
+    /// pruning point indicates corruption.
+    ///
+    /// Expected to be called before the first processing message is accepted
+    pub fn reconcile_pruning_state(&self) -> PruningRecoveryResult<()> {
+        let pruning_point_read = self.pruning_point_store.read();
+        let pruning_point = pruning_point_read.pruning_point().unwrap();
+        let history_root = pruning_point_read.history_root().unwrap_option();
+        let last_pruned = pruning_point_read.last_pruned().unwrap_option();
+        drop(pruning_point_read);
+
+        let Some(last_pruned) = last_pruned else {
+            if let Some(history_root) = history_root.filter(|&root| root != pruning_point) {
+                info!("A data prune from history root {} up to pruning point {} is pending", history_root, pruning_point);
+            }
+            return Ok(());
         };
 
-        // On start-up, check if any pruning workflows require recovery. We wait for the first processing message to arrive
-        // in order to make sure the node is already connected and receiving blocks before we start background recovery operations
-        self.recover_pruning_workflows_if_needed();
-        self.advance_pruning_point_and_candidate_if_possible(sink_ghostdag_data);
+        if !self.is_below_pruning_point(last_pruned, pruning_point) {
+            return Err(PruningRecoveryError::LastPrunedNotBelowPruningPoint { last_pruned, pruning_point });
+        }
+        // The marker is cleared in the same batch which sets the history root to the pruning point, so a marker along with a
+        // history root which equals the pruning point can only be left by a re-prune up to the current pruning point
+        if history_root == Some(pruning_point) {
+            info!(
+                "Found an interrupted data prune up to the current pruning point {} (last pruned block: {}); it will be resumed once block processing starts",
+                pruning_point, last_pruned
+            );
+            return Ok(());
+        }
+
+        info!(
+            "Found an interrupted data prune up to pruning point {} (history root: {}, last pruned block: {}); it will be resumed once block processing starts",
+            pruning_point,
+            history_root.map_or_else(|| "none".to_string(), |root| root.to_string()),
+            last_pruned
+        );
+        Ok(())
+    }
 
-        while let Ok(PruningProcessingMessage::Process { sink_ghostdag_data }) = self.receiver.recv() {
-            self.advance_pruning_point_and_candidate_if_possible(sink_ghostdag_data);
+    /// Returns whether `block` is neither the pruning point nor in its future. Blocks whose reachability data was already
+    /// pruned are compared by blue score, while blocks which were pruned entirely are below the pruning point by construction
+    fn is_below_pruning_point(&self, block: Hash, pruning_point: Hash) -> bool {
+        if block == pruning_point {
+            return false;
+        }
+        let reachability_read = self.reachability_store.read();
+        if reachability_read.has(block).unwrap() {
+            return !reachability_read.is_dag_ancestor_of_result(pruning_point, block).unwrap();
+        }
+        drop(reachability_read);
+        match self.headers_store.get_blue_score(block).unwrap_option() {
+            Some(blue_score) => blue_score < self.headers_store.get_blue_score(pruning_point).unwrap(),
+            None => true,
         }
     }
 
     fn recover_pruning_workflows_if_needed(&self) {
+        if self.config.verify_past_pruning_points_on_startup {
+            info!("Verifying the past pruning points chain");
+            if let Err(err) = self.verify_past_pruning_points_chain() {
+                panic!("The past pruning points store is inconsistent ({}); the node must be resynced", err);
+            }
+        }
+
         let pruning_point_read = self.pruning_point_store.read();
         let pruning_point = pruning_point_read.pruning_point().unwrap();
         let history_root = pruning_point_read.history_root().unwrap_option();
@@ -155,26 +445,116 @@ impl PruningProcessor {
 
         if let Some(history_root) = history_root {
             // This indicates the node crashed or was forced to stop during a former data prune operation hence
-            // we need to complete it
-            if history_root != pruning_point {
-                self.prune(pruning_point);
+            // we need to complete it. A marker with an up-to-date history root was left by an interrupted re-prune up to
+            // the current pruning point, which is resumed as well
+            let last_pruned = self.pruning_point_store.read().last_pruned().unwrap_option();
+            if history_root != pruning_point || last_pruned.is_some() {
+                if let Some(last_pruned) = last_pruned {
+                    info!(
+                        "Resuming the interrupted data prune up to pruning point {} (last pruned block: {})",
+                        pruning_point, last_pruned
+                    );
+                }
+                if let Err(err) = self.prune_or_defer(pruning_point) {
+                    warn!("Resuming the interrupted data prune failed: {} -- will retry with the next processing message", err);
+                }
             }
         }
 
         // TODO: both `pruning_utxoset_position` and `history_root` are new DB keys so for now we assume correct state if the keys are missing
     }
 
-    fn advance_pruning_point_and_candidate_if_possible(&self, sink_ghostdag_data: CompactGhostdagData) {
+    /// Verifies that the past pruning points store holds a contiguous chain from genesis up to the current pruning point,
+    /// reporting the first broken index. Consecutive entries are checked for DAG ancestry via reachability when both still
+    /// have reachability data, and otherwise (i.e., for entries whose reachability data was pruned) by requiring strictly
+    /// increasing blue scores
+    pub fn verify_past_pruning_points_chain(&self) -> PruningVerificationResult<()> {
+        let pruning_info = self.pruning_point_store.read().get().unwrap();
+        let reachability_read = self.reachability_store.read();
+        let mut previous: Option<(Hash, u64)> = None;
+        for index in 0..=pruning_info.index {
+            let Some(current) = self.past_pruning_points_store.get(index).unwrap_option() else {
+                return Err(PruningVerificationError::MissingPastPruningPoint(index));
+            };
+            let Some(blue_score) = self.headers_store.get_blue_score(current).unwrap_option() else {
+                return Err(PruningVerificationError::MissingPastPruningPoint(index));
+            };
+            match previous {
+                None if current != self.config.genesis.hash => {
+                    return Err(PruningVerificationError::PastPruningPointsRootMismatch(current));
+                }
+                Some((previous, previous_blue_score)) => {
+                    let connected = if reachability_read.has(previous).unwrap() && reachability_read.has(current).unwrap() {
+                        reachability_read.is_dag_ancestor_of_result(previous, current).unwrap()
+                    } else {
+                        previous_blue_score < blue_score
+                    };
+                    if !connected {
+                        return Err(PruningVerificationError::BrokenPastPruningPointsChain { index, previous, current });
+                    }
+                }
+                None => {}
+            }
+            previous = Some((current, blue_score));
+        }
+        let tip = previous.expect("the range includes at least index zero").0;
+        if tip != pruning_info.pruning_point {
+            return Err(PruningVerificationError::PastPruningPointsTipMismatch { tip, pruning_point: pruning_info.pruning_point });
+        }
+        Ok(())
+    }
+
+    /// Estimates how far the next pruning point movement is, given the current sink and pruning point info. Returns the
+    /// number of remaining blue score units along with the expected duration according to the target block rate, or `None`
+    /// if a movement is already due. This is a read-only query which does not affect the pruning store
+    pub fn estimate_next_pruning_point_movement(
+        &self,
+        sink_ghostdag_data: CompactGhostdagData,
+        current_pruning_info: &PruningPointInfo,
+    ) -> Option<(u64, Duration)> {
+        let remaining = self
+            .pruning_point_manager
+            .blue_score_until_next_pruning_point_movement(sink_ghostdag_data, current_pruning_info.pruning_point)?;
+        // Blue score advances by roughly one unit per block
+        Some((remaining, Duration::from_millis(remaining * self.config.target_time_per_block)))
+    }
+
+    /// Advances the pruning point (and candidate) according to the pruning and finality depths of the network params, which
+    /// might be overridden per network (e.g., shallow depths on devnet/simnet, see `Params::validate_pruning_depths`)
+    fn advance_pruning_point_and_candidate_if_possible(&self, sink_ghostdag_data: CompactGhostdagData) -> PruningProcessingResult<()> {
         let pruning_point_read = self.pruning_point_store.upgradable_read();
         let current_pruning_info = pruning_point_read.get().unwrap();
-        let (new_pruning_points, new_candidate) = self.pruning_point_manager.next_pruning_points_and_candidate_by_ghostdag_data(
-            sink_ghostdag_data,
-            None,
-            current_pruning_info.candidate,
-            current_pruning_info.pruning_point,
-        );
+        let (mut new_pruning_points, mut new_candidate) =
+            self.pruning_point_manager.next_pruning_points_and_candidate_by_ghostdag_data(
+                sink_ghostdag_data,
+                None,
+                current_pruning_info.candidate,
+                current_pruning_info.pruning_point,
+            );
+
+        // Apply a partial advance if the movement exceeds the configured bound (a zero bound is treated as one, since
+        // otherwise the pruning point could never move)
+        if let Some(max_advances) = self.config.max_pruning_advances_per_cycle.map(|max| max.max(1)) {
+            if new_pruning_points.len() > max_advances {
+                info!(
+                    "Pruning point movement: applying {} out of {} pending advances, the rest are left for the next cycles",
+                    max_advances,
+                    new_pruning_points.len()
+                );
+                new_pruning_points.truncate(max_advances);
+                // The candidate search starts from the stored candidate, so it must not skip past the remaining advances.
+                // Setting it to the last applied pruning point resumes the search from there on the next movement
+                new_candidate = *new_pruning_points.last().unwrap();
+            }
+        }
 
         if !new_pruning_points.is_empty() {
+            // Validate the movement before mutating any store, so that an inconsistency aborts the movement cleanly
+            self.validate_pruning_point_movement(current_pruning_info.pruning_point, &new_pruning_points)?;
+            if self.config.pruning_utxo_diffs_precheck {
+                self.verify_chain_utxo_diffs_exist(current_pruning_info.pruning_point, *new_pruning_points.last().unwrap())?;
+            }
+
             // Update past pruning points and pruning point stores
             let mut batch = WriteBatch::default();
             let mut pruning_point_write = RwLockUpgradableReadGuard::upgrade(pruning_point_read);
@@ -184,179 +564,477 @@ impl PruningProcessor {
             let new_pp_index = current_pruning_info.index + new_pruning_points.len() as u64;
             let new_pruning_point = *new_pruning_points.last().unwrap();
             pruning_point_write.set_batch(&mut batch, new_pruning_point, new_candidate, new_pp_index).unwrap();
-            self.db.write(batch).unwrap();
+            if let Err(err) = self.write_batch_with_retry(batch) {
+                // Revert the cached changes so that the next processing message retries the movement from the current state
+                for (i, past_pp) in new_pruning_points.iter().copied().enumerate() {
+                    self.past_pruning_points_store.revert_cached_insert(current_pruning_info.index + i as u64 + 1, past_pp);
+                }
+                pruning_point_write
+                    .set_batch(
+                        &mut WriteBatch::default(),
+                        current_pruning_info.pruning_point,
+                        current_pruning_info.candidate,
+                        current_pruning_info.index,
+                    )
+                    .unwrap();
+                return Err(err);
+            }
+            // Invalidate the cached proof and trusted data while still holding the pruning point write lock, i.e., before
+            // any reader can observe the new pruning point
+            self.pruning_proof_manager.invalidate_cached_pruning_point_data(new_pruning_point);
             drop(pruning_point_write);
 
+            // Notify subscribers of the movement now that it is committed
+            self.notification_root
+                .notify(Notification::PruningPointMoved(PruningPointMovedNotification::new(
+                    current_pruning_info.pruning_point,
+                    new_pruning_point,
+                    new_pp_index,
+                )))
+                .expect("expecting an open unbounded channel");
+
             // Inform the user
             info!("Periodic pruning point movement: advancing from {} to {}", current_pruning_info.pruning_point, new_pruning_point);
 
             // Advance the pruning point utxoset to the state of the new pruning point using chain-block UTXO diffs
             if !self.advance_pruning_utxoset(current_pruning_info.pruning_point, new_pruning_point) {
                 info!("Interrupted while advancing the pruning point UTXO set: Process is exiting");
-                return;
+                return Ok(());
             }
             info!("Updated the pruning point UTXO set");
 
-            // Finally, prune data in the new pruning point past
-            self.prune(new_pruning_point);
+            // Finally, prune data in the new pruning point past (or defer it if pruning is currently not allowed)
+            self.prune_or_defer(new_pruning_point)?;
         } else if new_candidate != current_pruning_info.candidate {
             let mut pruning_point_write = RwLockUpgradableReadGuard::upgrade(pruning_point_read);
             pruning_point_write.set(current_pruning_info.pruning_point, new_candidate, current_pruning_info.index).unwrap();
         }
+        Ok(())
+    }
+
+    /// A consistency check of a pruning point movement, performed before the pruning point store and the pruning point UTXO
+    /// set are mutated. Each new pruning point must be a selected chain descendant of its predecessor and must have the header
+    /// and GHOSTDAG data required for building its proof and anticone. The proof of the final pruning point must be headed by
+    /// it and end at genesis, and its anticone (once final) must start with it
+    fn validate_pruning_point_movement(
+        &self,
+        current_pruning_point: Hash,
+        new_pruning_points: &[Hash],
+    ) -> PruningProcessingResult<()> {
+        let mut previous = current_pruning_point;
+        for &new in new_pruning_points {
+            if !self.headers_store.has(new).unwrap() || !self.ghostdag_store.has(new).unwrap() {
+                return Err(PruningProcessingError::MissingPruningPointData(new));
+            }
+            if !self.reachability_service.is_chain_ancestor_of(previous, new) {
+                return Err(PruningProcessingError::PruningPointNotChainDescendant { previous, new });
+            }
+            previous = new;
+        }
+
+        // Only the last pruning point is committed, hence only its proof and anticone are required. Both are cached by the
+        // pruning proof manager, so the following data prune (and proof requests of peers) reuse them
+        let Some(&pruning_point) = new_pruning_points.last() else {
+            return Ok(());
+        };
+        let proof = self.pruning_proof_manager.get_pruning_point_proof_cached_for(pruning_point);
+        let proof_head = proof.first().and_then(|level| level.last()).map(|header| header.hash);
+        if proof_head != Some(pruning_point) {
+            return Err(PruningProcessingError::PruningProofHeadMismatch { pruning_point, proof_head });
+        }
+        let proof_tail = proof.last().and_then(|level| level.last()).map(|header| header.hash);
+        if proof_tail != Some(self.config.genesis.hash) {
+            return Err(PruningProcessingError::PruningProofTailNotGenesis { pruning_point, proof_tail });
+        }
+        // The anticone is available only once it is final. Otherwise the data prune is skipped until it is (see `prune_core`)
+        if let Ok(data) = self.pruning_proof_manager.get_pruning_point_anticone_and_trusted_data_for(pruning_point) {
+            let anticone_head = data.anticone.first().copied();
+            if anticone_head != Some(pruning_point) {
+                return Err(PruningProcessingError::AnticoneHeadMismatch { pruning_point, anticone_head });
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies that all chain blocks in `(utxoset_position, new_pruning_point]` have a UTXO diff, i.e., that the pruning point
+    /// UTXO set can be advanced up to `new_pruning_point` (see `Config::pruning_utxo_diffs_precheck`). Similarly to reading the
+    /// diffs when applying them, existence is checked concurrently (and only the keys are queried)
+    fn verify_chain_utxo_diffs_exist(&self, utxoset_position: Hash, new_pruning_point: Hash) -> PruningProcessingResult<()> {
+        // The stored position takes precedence, see `advance_pruning_utxoset`
+        let utxoset_position = self.pruning_utxoset_stores.read().utxoset_position().unwrap_option().unwrap_or(utxoset_position);
+        let chain = self.reachability_service.forward_chain_iterator(utxoset_position, new_pruning_point, true).skip(1);
+        for chunk in &chain.chunks(64 * self.config.pruning_utxo_diffs_readahead.max(1)) {
+            let chain_blocks = chunk.collect_vec();
+            if let Some(&chain_block) =
+                chain_blocks.par_iter().find_first(|&&chain_block| !self.utxo_diffs_store.has(chain_block).unwrap())
+            {
+                return Err(PruningProcessingError::MissingChainUtxoDiff { chain_block, pruning_point: new_pruning_point });
+            }
+        }
+        Ok(())
     }
 
     fn advance_pruning_utxoset(&self, utxoset_position: Hash, new_pruning_point: Hash) -> bool {
         let mut pruning_utxoset_write = self.pruning_utxoset_stores.write();
-        for chain_block in self.reachability_service.forward_chain_iterator(utxoset_position, new_pruning_point, true).skip(1) {
+        // The stored position (if recorded) takes precedence, since the set might have been concurrently
+        // rebuilt up to the new pruning point (see `rebuild_pruning_point_utxo_set`)
+        let utxoset_position = pruning_utxoset_write.utxoset_position().unwrap_option().unwrap_or(utxoset_position);
+        // The running multiset is seeded with the stored multiset of the current position, if it was not pruned yet
+        let mut running_multiset = match self.config.incremental_utxo_commitment_check {
+            true => self.utxo_multisets_store.get(utxoset_position).unwrap_option(),
+            false => None,
+        };
+        if !self.apply_chain_utxo_diffs(&mut pruning_utxoset_write, utxoset_position, new_pruning_point, running_multiset.as_mut()) {
+            return false;
+        }
+        drop(pruning_utxoset_write);
+
+        if let Some(observer) = self.observer.get() {
+            observer.on_utxo_set_moved(utxoset_position, new_pruning_point);
+        }
+
+        if let Some(multiset) = running_multiset {
+            self.assert_incremental_utxo_commitment(new_pruning_point, multiset);
+        } else if self.config.incremental_utxo_commitment_check {
+            info!(
+                "The UTXO multiset of {} is unavailable for an incremental check, verifying the full UTXO set commitment instead",
+                utxoset_position
+            );
+            self.assert_utxo_commitment(new_pruning_point);
+        } else if self.config.effective_pruning_sanity_checks().utxo_commitment {
+            info!("Performing a sanity check that the new UTXO set has the expected UTXO commitment");
+            self.assert_utxo_commitment(new_pruning_point);
+        }
+        true
+    }
+
+    /// Applies the UTXO diffs of all chain blocks in `(utxoset_position, target]` to the pruning point UTXO set, updating
+    /// the recorded position along the way. If provided, the diffs are also folded into `running_multiset`. Returns false
+    /// if interrupted by an exit signal
+    fn apply_chain_utxo_diffs(
+        &self,
+        pruning_utxoset_write: &mut PruningUtxosetStores,
+        utxoset_position: Hash,
+        target: Hash,
+        mut running_multiset: Option<&mut MuHash>,
+    ) -> bool {
+        // Diffs must be applied sequentially in chain order, however reading them is independent. Hence the diffs of the
+        // next (up to) `readahead` chain blocks are read concurrently while the current ones are being applied
+        let readahead = self.config.pruning_utxo_diffs_readahead.max(1);
+        let mut chain = self.reachability_service.forward_chain_iterator(utxoset_position, target, true).skip(1);
+        let read_diffs = |chain_blocks: Vec<Hash>| {
+            chain_blocks
+                .into_par_iter()
+                .map(|chain_block| (chain_block, self.utxo_diffs_store.get(chain_block).expect("chain blocks have utxo state")))
+                .collect::<Vec<_>>()
+        };
+        let mut utxo_diffs = read_diffs(chain.by_ref().take(readahead).collect_vec());
+        while !utxo_diffs.is_empty() {
+            let next_chain_blocks = chain.by_ref().take(readahead).collect_vec();
+            let (next_utxo_diffs, completed) = rayon::join(
+                || read_diffs(next_chain_blocks),
+                || self.apply_utxo_diffs(pruning_utxoset_write, &utxo_diffs, running_multiset.as_deref_mut()),
+            );
+            if !completed {
+                return false;
+            }
+            utxo_diffs = next_utxo_diffs;
+        }
+        true
+    }
+
+    /// Applies `utxo_diffs` (given in chain order) to the pruning point UTXO set, see [`Self::apply_chain_utxo_diffs`]
+    fn apply_utxo_diffs(
+        &self,
+        pruning_utxoset_write: &mut PruningUtxosetStores,
+        utxo_diffs: &[(Hash, Arc<UtxoDiff>)],
+        mut running_multiset: Option<&mut MuHash>,
+    ) -> bool {
+        for (chain_block, utxo_diff) in utxo_diffs.iter() {
             if self.is_consensus_exiting.load(Ordering::Relaxed) {
                 return false;
             }
-            let utxo_diff = self.utxo_diffs_store.get(chain_block).expect("chain blocks have utxo state");
+            if let Some(multiset) = running_multiset.as_deref_mut() {
+                for (outpoint, entry) in utxo_diff.removed().iter() {
+                    multiset.remove_utxo(outpoint, entry);
+                }
+                for (outpoint, entry) in utxo_diff.added().iter() {
+                    multiset.add_utxo(outpoint, entry);
+                }
+            }
             let mut batch = WriteBatch::default();
             pruning_utxoset_write.utxo_set.write_diff_batch(&mut batch, utxo_diff.as_ref()).unwrap();
-            pruning_utxoset_write.set_utxoset_position(&mut batch, chain_block).unwrap();
-            self.db.write(batch).unwrap();
+            pruning_utxoset_write.set_utxoset_position(&mut batch, *chain_block).unwrap();
+            self.write_committed_batch_or_panic(batch);
+        }
+        true
+    }
+
+    /// Rebuilds the pruning point UTXO set from scratch by applying the UTXO diffs of all selected chain blocks from
+    /// genesis up to the current pruning point, and verifies the result against the pruning point UTXO commitment.
+    ///
+    /// This is a maintenance operation for recovering from a corrupted pruning point UTXO set. It is only possible
+    /// as long as the chain UTXO diffs were not pruned yet (e.g., on archival nodes), otherwise an error is returned
+    /// before any data is modified. Must not be called while holding a consensus session (the pruning lock is acquired for write)
+    pub fn rebuild_pruning_point_utxo_set(&self) -> PruningRecoveryResult<()> {
+        // Hold the pruning lock throughout in order to block data pruning and consensus processing meanwhile. We also
+        // hold the pruning point store upgradable lock so that the pruning point cannot move during the rebuild
+        let prune_guard = self.pruning_lock.blocking_write();
+        let pruning_point_read = self.pruning_point_store.upgradable_read();
+        let pruning_point = pruning_point_read.pruning_point().unwrap();
+        let genesis = self.config.genesis.hash;
+
+        // Make sure all required diffs are available before clearing the store
+        let chain = self.reachability_service.forward_chain_iterator(genesis, pruning_point, true).skip(1).collect_vec();
+        if let Some(&missing) = chain.iter().find(|&&chain_block| self.utxo_diffs_store.get(chain_block).unwrap_option().is_none()) {
+            return Err(PruningRecoveryError::MissingUtxoDiff(missing));
+        }
+
+        info!(
+            "Rebuilding the pruning point UTXO set from {} chain block UTXO diffs up to pruning point {}",
+            chain.len(),
+            pruning_point
+        );
+        let mut pruning_utxoset_write = self.pruning_utxoset_stores.write();
+        pruning_utxoset_write.utxo_set.clear().unwrap();
+        let mut batch = WriteBatch::default();
+        pruning_utxoset_write.set_utxoset_position(&mut batch, genesis).unwrap();
+        self.write_batch_with_retry(batch)?;
+
+        // From here on the recorded position reflects the progress, so an interrupted rebuild is
+        // completed by the recovery workflow on the next startup
+        if !self.apply_chain_utxo_diffs(&mut pruning_utxoset_write, genesis, pruning_point, None) {
+            return Err(PruningRecoveryError::Interrupted);
         }
         drop(pruning_utxoset_write);
 
-        if self.config.enable_sanity_checks {
-            info!("Performing a sanity check that the new UTXO set has the expected UTXO commitment");
-            self.assert_utxo_commitment(new_pruning_point);
+        let expected = self.headers_store.get_header(pruning_point).unwrap().utxo_commitment;
+        let actual = self.compute_pruning_utxo_multiset().finalize();
+        drop(pruning_point_read);
+        drop(prune_guard);
+
+        if actual != expected {
+            return Err(PruningRecoveryError::UtxoCommitmentMismatch { pruning_point, expected, actual });
         }
-        true
+        info!("The pruning point UTXO set was rebuilt successfully and matches the UTXO commitment of {}", pruning_point);
+        Ok(())
     }
 
     fn assert_utxo_commitment(&self, pruning_point: Hash) {
         info!("Verifying the new pruning point UTXO commitment (sanity test)");
         let commitment = self.headers_store.get_header(pruning_point).unwrap().utxo_commitment;
-        let mut multiset = MuHash::new();
-        let pruning_utxoset_read = self.pruning_utxoset_stores.read();
-        for (outpoint, entry) in pruning_utxoset_read.utxo_set.iterator().map(|r| r.unwrap()) {
-            multiset.add_utxo(&outpoint, &entry);
-        }
+        let multiset = self.compute_pruning_utxo_multiset();
         assert_eq!(multiset.finalize(), commitment, "Updated pruning point utxo set does not match the header utxo commitment");
         info!("Pruning point UTXO commitment was verified correctly (sanity test)");
     }
 
-    fn prune(&self, new_pruning_point: Hash) {
+    /// Verifies the UTXO commitment of the new pruning point against a multiset which was maintained while applying the chain
+    /// UTXO diffs, avoiding a full scan of the pruning point UTXO set
+    fn assert_incremental_utxo_commitment(&self, pruning_point: Hash, multiset: MuHash) {
+        let commitment = self.headers_store.get_header(pruning_point).unwrap().utxo_commitment;
+        assert_eq!(
+            multiset.finalize(),
+            commitment,
+            "Incrementally computed pruning point utxo multiset does not match the header utxo commitment"
+        );
+        info!("Pruning point UTXO commitment was verified incrementally");
+    }
+
+    /// Computes the multiset of the pruning point UTXO set. The set is folded in fixed-size chunks and the UTXO set
+    /// lock is released in between chunks, so that other readers of the set are not starved for long periods.
+    ///
+    /// Note: the result reflects a single consistent UTXO set only if the pruning point does not move concurrently,
+    /// which is guaranteed when called from within the pruning processor
+    pub fn compute_pruning_utxo_multiset(&self) -> MuHash {
+        let mut multiset = MuHash::new();
+        let mut from_outpoint = None;
+        loop {
+            let pruning_utxoset_read = self.pruning_utxoset_stores.read();
+            let chunk = pruning_utxoset_read
+                .utxo_set
+                .seek_iterator(from_outpoint, UTXO_MULTISET_CHUNK_SIZE, from_outpoint.is_some())
+                .map(|r| r.unwrap())
+                .collect_vec();
+            drop(pruning_utxoset_read);
+
+            for (outpoint, entry) in chunk.iter() {
+                multiset.add_utxo(outpoint, entry);
+            }
+            if chunk.len() < UTXO_MULTISET_CHUNK_SIZE {
+                return multiset;
+            }
+            from_outpoint = chunk.last().map(|&(outpoint, _)| outpoint);
+        }
+    }
+
+    fn prune(&self, new_pruning_point: Hash, genesis: Hash) -> PruningProcessingResult<()> {
+        self.prune_core(new_pruning_point, genesis).map(|_| ())
+    }
+
+    /// Prunes data in the past of `new_pruning_point` and returns a structured report of the run (counts and per-phase
+    /// durations). Shares the exact code path of the regular pruning workflow, and is meant for benchmarks and diagnostics.
+    ///
+    /// Note: the caller is expected to have already advanced the pruning point to `new_pruning_point`
+    pub fn prune_with_report(&self, new_pruning_point: Hash) -> PruningProcessingResult<PruningReport> {
+        self.prune_core(new_pruning_point, self.config.genesis.hash)
+    }
+
+    fn prune_core(&self, new_pruning_point: Hash, genesis: Hash) -> PruningProcessingResult<PruningReport> {
         if self.config.is_archival {
-            warn!("The node is configured as an archival node -- avoiding data pruning. Note this might lead to heavy disk usage.");
-            return;
+            if !self.archival_warning_emitted.swap(true, Ordering::Relaxed) {
+                warn!(
+                    "The node is configured as an archival node -- avoiding data pruning. Note this might lead to heavy disk usage."
+                );
+            } else {
+                debug!("[PRUNING PROCESSOR] archival node, avoiding data pruning up to {}", new_pruning_point);
+            }
+            return Ok(PruningReport::default());
         }
+        let _prune_span = info_span!("prune", %new_pruning_point).entered();
+        let prune_start = Instant::now();
+        let previous_pruning_point = self.pruning_point_store.read().history_root().unwrap_option();
 
         info!("Header and Block pruning: preparing proof and anticone data...");
 
-        let proof = self.pruning_proof_manager.get_pruning_point_proof();
-        let data = self
-            .pruning_proof_manager
-            .get_pruning_point_anticone_and_trusted_data()
-            .expect("insufficient depth error is unexpected here");
-
-        let genesis = self.past_pruning_points_store.get(0).unwrap();
-
-        assert_eq!(new_pruning_point, proof[0].last().unwrap().hash);
-        assert_eq!(new_pruning_point, data.anticone[0]);
-        assert_eq!(genesis, self.config.genesis.hash);
-        assert_eq!(genesis, proof.last().unwrap().last().unwrap().hash);
-
-        // We keep full data for pruning point and its anticone, relations for DAA/GD
-        // windows and pruning proof, and only headers for past pruning points
-        let keep_blocks: BlockHashSet = data.anticone.iter().copied().collect();
-        let mut keep_relations: BlockHashMap<BlockLevel> = std::iter::empty()
-            .chain(data.anticone.iter().copied())
-            .chain(data.daa_window_blocks.iter().map(|th| th.header.hash))
-            .chain(data.ghostdag_blocks.iter().map(|gd| gd.hash))
-            .chain(proof[0].iter().map(|h| h.hash))
-            .map(|h| (h, 0)) // Mark block level 0 for all the above. Note that below we add the remaining levels
-            .collect();
-        let keep_headers: BlockHashSet = self.past_pruning_points();
+        // Both are obtained for `new_pruning_point` explicitly, so they are headed by it by construction. Their consistency is
+        // verified before the movement is committed (see `validate_pruning_point_movement`)
+        let proof = self.pruning_proof_manager.get_pruning_point_proof_cached_for(new_pruning_point);
+        let data = match self.pruning_proof_manager.get_pruning_point_anticone_and_trusted_data_for(new_pruning_point) {
+            Ok(data) => data,
+            Err(ConsensusError::PruningPointInsufficientDepth) => {
+                // Skipping is safe: the pruning point and its UTXO set were already advanced, and all data in its past is still
+                // present and consistent, just not yet pruned. Since the history root is not updated, the next movement (or the
+                // recovery workflow on restart) will retry pruning this data
+                warn!(
+                    "Header and Block pruning: the pruning point {} is not yet at sufficient depth -- skipping this pruning cycle",
+                    new_pruning_point
+                );
+                return Ok(PruningReport::default());
+            }
+            Err(err) => panic!("unexpected error while obtaining the pruning point anticone and trusted data: {}", err),
+        };
+
+        // The first past pruning point is expected to always be genesis. We catch any divergence of this store in debug builds
+        debug_assert_eq!(genesis, self.past_pruning_points_store.get(0).unwrap());
+
+        // Obtain the tips snapshot before computing the plan. Tips which become prunable only later are handled by the next pruning
+        let tips = self.body_tips_store.read().get().unwrap().read().iter().copied().collect_vec();
+        // Roots parents at each higher level, see `PruningPlan::new` for the rationale
+        let roots_parents_at_level = |level: BlockLevel| {
+            data.anticone
+                .iter()
+                .copied()
+                .map(|hash| self.headers_store.get_header_with_block_level(hash).expect("pruning point anticone is not pruned"))
+                .filter(|root| level > root.block_level) // If the root itself is at level, there's no need for its level-parents
+                .flat_map(|root| self.parents_manager.parents_at_level(&root.header, level).iter().copied().collect_vec())
+                .collect_vec()
+        };
+        let plan = PruningPlan::new(
+            new_pruning_point,
+            &proof,
+            &data,
+            roots_parents_at_level,
+            tips,
+            &self.reachability_service,
+            self.config.retain_all_headers,
+            self.config.retain_kept_window_caches,
+            self.config
+                .acceptance_data_retention
+                .map(|depth| self.headers_store.get_blue_score(new_pruning_point).unwrap().saturating_sub(depth)),
+        );
+        let plan_duration = prune_start.elapsed();
+        // Past pruning points are kept as headers only. Membership is tested via the store reverse index (see the loop below).
+        // The store holds the points at indices `0..=index`, genesis included
+        let past_pruning_points_count = self.pruning_point_store.read().get().unwrap().index + 1;
 
         info!("Header and Block pruning: waiting for consensus write permissions...");
 
         let mut prune_guard = self.pruning_lock.blocking_write();
 
+        // Safety invariant: all virtual parents must be in the future of the new pruning point, otherwise we would delete data
+        // which virtual still depends on. This is checked under the pruning lock, where virtual cannot be updated concurrently.
+        // Similarly to the insufficient depth case, skipping leaves the history root as is, so the next pruning retries
+        let virtual_parents_outside =
+            virtual_parents_outside_future(new_pruning_point, &self.lkg_virtual_state.load().parents, &self.reachability_service);
+        if !virtual_parents_outside.is_empty() {
+            drop(prune_guard);
+            warn!(
+                "Header and Block pruning: virtual parents {} are not in the future of the pruning point {} -- skipping this pruning cycle",
+                virtual_parents_outside.iter().reusable_format(", "),
+                new_pruning_point
+            );
+            return Ok(PruningReport::default());
+        }
+
         info!("Starting Header and Block pruning...");
 
+        let mut stats = PruningStats { previous_pruning_point, pruning_point: new_pruning_point, plan_duration, ..Default::default() };
+
         {
+            let span = info_span!(
+                "prune_ghostdag_updates",
+                %new_pruning_point,
+                updated = field::Empty,
+                selected_parent_reassignments = field::Empty
+            )
+            .entered();
+            let start = Instant::now();
             let mut counter = 0;
             let mut batch = WriteBatch::default();
-            // At this point keep_relations only holds level-0 relations which is the correct filtering criteria for primary GHOSTDAG
-            for kept in keep_relations.keys().copied() {
+            // Level-0 relations are the correct filtering criteria for primary GHOSTDAG.
+            // We iterate in sorted order so that the sequence of ghostdag updates is deterministic (the resulting state is not
+            // affected by the order, however a stable order allows tests to compare the written batch)
+            for kept in plan.keep_relations.iter().filter(|(_, &level)| level == 0).map(|(&hash, _)| hash).sorted() {
                 let Some(ghostdag) = self.ghostdag_store.get_data(kept).unwrap_option() else {
                     continue;
                 };
-                if ghostdag.unordered_mergeset().any(|h| !keep_relations.contains_key(&h)) {
+                if ghostdag.unordered_mergeset().any(|h| !plan.keeps_level_zero_relations(h)) {
                     let mut mutable_ghostdag: ExternalGhostdagData = ghostdag.as_ref().into();
-                    mutable_ghostdag.mergeset_blues.retain(|h| keep_relations.contains_key(h));
-                    mutable_ghostdag.mergeset_reds.retain(|h| keep_relations.contains_key(h));
-                    mutable_ghostdag.blues_anticone_sizes.retain(|k, _| keep_relations.contains_key(k));
-                    if !keep_relations.contains_key(&mutable_ghostdag.selected_parent) {
+                    mutable_ghostdag.mergeset_blues.retain(|&h| plan.keeps_level_zero_relations(h));
+                    mutable_ghostdag.mergeset_reds.retain(|&h| plan.keeps_level_zero_relations(h));
+                    mutable_ghostdag.blues_anticone_sizes.retain(|&k, _| plan.keeps_level_zero_relations(k));
+                    if !plan.keeps_level_zero_relations(mutable_ghostdag.selected_parent) {
                         mutable_ghostdag.selected_parent = ORIGIN;
+                        stats.selected_parent_reassignments += 1;
                     }
+                    debug_assert!(
+                        is_coherent_ghostdag_rewrite(&mutable_ghostdag, &plan),
+                        "incoherent GHOSTDAG rewrite of retained block {}",
+                        kept
+                    );
                     counter += 1;
                     self.ghostdag_store.update_batch(&mut batch, kept, &Arc::new(mutable_ghostdag.into())).unwrap();
                 }
             }
-            self.db.write(batch).unwrap();
-            info!("Header and Block pruning: updated ghostdag data for {} blocks", counter);
+            // The GHOSTDAG cache already reflects the rewrite, so a retried prune would not detect (and rewrite) stale entries
+            self.write_committed_batch_or_panic(batch);
+            stats.ghostdag_updates_duration = start.elapsed();
+            span.record("updated", counter).record("selected_parent_reassignments", stats.selected_parent_reassignments);
+            info!(
+                "Header and Block pruning: updated ghostdag data for {} blocks (selected parent reassigned to ORIGIN for {})",
+                counter, stats.selected_parent_reassignments
+            );
         }
 
-        // No need to hold the prune guard while we continue populating keep_relations
-        drop(prune_guard);
-
-        // Add additional levels only after filtering GHOSTDAG data via level 0
-        for (level, level_proof) in proof.iter().enumerate().skip(1) {
-            let level = level as BlockLevel;
-            // We obtain the headers of the pruning point anticone (including the pruning point)
-            // in order to mark all parents of anticone roots at level as not-to-be-deleted.
-            // This optimizes multi-level parent validation (see ParentsManager)
-            // by avoiding the deletion of high-level parents which might still be needed for future
-            // header validation (avoiding the need for reference blocks; see therein).
-            //
-            // Notes:
-            //
-            // 1. Normally, such blocks would be part of the proof for this level, but here we address the rare case
-            //    where there are a few such parallel blocks (since the proof only contains the past of the pruning point's
-            //    selected-tip-at-level)
-            // 2. We refer to the pp anticone as roots even though technically it might contain blocks which are not a pure
-            //    antichain (i.e., some of them are in the past of others). These blocks only add redundant info which would
-            //    be included anyway.
-            let roots_parents_at_level = data
-                .anticone
-                .iter()
-                .copied()
-                .map(|hash| self.headers_store.get_header_with_block_level(hash).expect("pruning point anticone is not pruned"))
-                .filter(|root| level > root.block_level) // If the root itself is at level, there's no need for its level-parents
-                .flat_map(|root| self.parents_manager.parents_at_level(&root.header, level).iter().copied().collect_vec());
-            for hash in level_proof.iter().map(|header| header.hash).chain(roots_parents_at_level) {
-                if let Vacant(e) = keep_relations.entry(hash) {
-                    // This hash was not added by any lower level -- mark it as affiliated with proof level `level`
-                    e.insert(level);
-                }
-            }
+        if self.config.effective_pruning_sanity_checks().ghostdag_closure {
+            // Checked before the traversal deletes anything, so a violation aborts the prune with no data lost
+            self.verify_keep_relations_closure(&plan.keep_relations).map_err(PruningProcessingError::GhostdagClosureViolation)?;
         }
 
-        prune_guard = self.pruning_lock.blocking_write();
         let mut lock_acquire_time = Instant::now();
         let mut reachability_read = self.reachability_store.upgradable_read();
 
         {
+            let _span =
+                info_span!("prune_tips_and_selected_chain", %new_pruning_point, pruned_tips = plan.pruned_tips.len()).entered();
             // Start with a batch for pruning body tips and selected chain stores
+            let start = Instant::now();
             let mut batch = WriteBatch::default();
 
-            // Prune tips which can no longer be merged by virtual.
-            // By the prunality proof, any tip which isn't in future(pruning_point) will never be merged
-            // by virtual and hence can be safely deleted
+            // Prune tips which can no longer be merged by virtual
             let mut tips_write = self.body_tips_store.write();
-            let pruned_tips = tips_write
-                .get()
-                .unwrap()
-                .read()
-                .iter()
-                .copied()
-                .filter(|&h| !reachability_read.is_dag_ancestor_of_result(new_pruning_point, h).unwrap())
-                .collect_vec();
-            tips_write.prune_tips_with_writer(BatchDbWriter::new(&mut batch), &pruned_tips).unwrap();
+            let pruned_tips = &plan.pruned_tips;
+            tips_write.prune_tips_with_writer(BatchDbWriter::new(&mut batch), pruned_tips).unwrap();
             if !pruned_tips.is_empty() {
                 info!(
                     "Header and Block pruning: pruned {} tips: {}...{}",
@@ -370,197 +1048,675 @@ impl PruningProcessor {
             let mut selected_chain_write = self.selected_chain_store.write();
             selected_chain_write.prune_below_pruning_point(BatchDbWriter::new(&mut batch), new_pruning_point).unwrap();
 
-            // Flush the batch to the DB
-            self.db.write(batch).unwrap();
+            // Flush the batch to the DB. The tips and selected chain caches were already pruned, so a retried prune would
+            // not delete the stale entries
+            self.write_committed_batch_or_panic(batch);
 
             // Calling the drops explicitly after the batch is written in order to avoid possible errors.
             drop(selected_chain_write);
             drop(tips_write);
+            stats.tips_and_chain_duration = start.elapsed();
         }
 
         // Now we traverse the anti-future of the new pruning point starting from origin and going up.
         // The most efficient way to traverse the entire DAG from the bottom-up is via the reachability tree
-        let mut queue = VecDeque::<Hash>::from_iter(reachability_read.get_children(ORIGIN).unwrap().iter().copied());
-        let (mut counter, mut traversed) = (0, 0);
-        info!("Header and Block pruning: starting traversal from: {} (genesis: {})", queue.iter().reusable_format(", "), genesis);
-        while let Some(current) = queue.pop_front() {
-            if reachability_read.is_dag_ancestor_of_result(new_pruning_point, current).unwrap() {
-                continue;
+        let mut queue_spill_store = DbPruningQueueStore::new(self.db.clone());
+        let deferred_frontier = queue_spill_store.deferred_frontier().unwrap_option();
+        let mut queue = match deferred_frontier.filter(|frontier| frontier.pruning_point == new_pruning_point) {
+            // A previous run for this pruning point stopped at its soft deadline, so the traversal is resumed from its frontier.
+            // This is valid since the blocks visited by that run were fully committed, and their tree children which were not
+            // yet visited are all in the frontier (blocks added since are in the pruning point future, hence never visited)
+            Some(frontier) => {
+                queue_spill_store.clear_deferred_frontier().unwrap();
+                info!(
+                    "Header and Block pruning: resuming the deferred traversal from {} queued blocks (genesis: {})",
+                    frontier.end - frontier.start,
+                    genesis
+                );
+                TraversalQueue::resume(self.config.pruning_queue_spill_threshold, queue_spill_store, frontier.start, frontier.end)
             }
-            traversed += 1;
-            // Obtain the tree children of `current` and push them to the queue before possibly being deleted below
-            queue.extend(reachability_read.get_children(current).unwrap().iter());
-
-            // If we have the lock for more than a few milliseconds, release and recapture to allow consensus progress during pruning
-            if lock_acquire_time.elapsed() > Duration::from_millis(5) {
+            None => {
+                // Clear any leftovers of a previously interrupted traversal (possibly run with a spill threshold which was since
+                // unset), or a frontier deferred by a run for a previous pruning point. Such a traversal restarts from ORIGIN
+                queue_spill_store.clear().unwrap();
+                let mut queue = TraversalQueue::new(self.config.pruning_queue_spill_threshold, queue_spill_store);
+                // Seed the walk with the tree children of ORIGIN, exactly as done by `reachability::antifuture_iterator`
+                reachability::extend_antifuture_walk(&mut &*reachability_read, ORIGIN, new_pruning_point, &mut queue);
+                info!(
+                    "Header and Block pruning: starting traversal from: {} (genesis: {})",
+                    reachability_read.get_children(ORIGIN).unwrap().iter().reusable_format(", "),
+                    genesis
+                );
+                queue
+            }
+        };
+        let (mut counter, mut traversed) = (0, 0);
+        let mut children_cache = ChildrenCache::new(PRUNING_CHILDREN_CACHE_SIZE);
+        let mut io_throttle = IoThrottle::new(self.config.pruning_io_budget);
+        let mut throttle_delay: Option<Duration> = None;
+        let mut batch_writer = (self.config.pruning_db_writer_threads > 0).then(|| {
+            PruningBatchWriter::new(
+                self.db.clone(),
+                self.config.pruning_db_writer_threads,
+                self.config.pruning_db_write_retries,
+                self.config.pruning_db_write_backoff,
+            )
+        });
+        let traversal_span =
+            info_span!("prune_traversal", %new_pruning_point, traversed = field::Empty, pruned = field::Empty).entered();
+        let traversal_start = Instant::now();
+        // The traversed count and time of the last progress log, see `Config::pruning_progress_log_interval`
+        let mut last_progress_log = (0, traversal_start);
+        while !queue.is_empty() {
+            // If we have the lock for more than a few milliseconds, release and recapture to allow consensus progress during pruning.
+            // We do the same if the IO budget requires throttling, in which case we sleep without holding the lock
+            if throttle_delay.is_some() || lock_acquire_time.elapsed() > Duration::from_millis(5) {
                 drop(reachability_read);
-                // An exit signal was received. Exit from this long running process.
-                if self.is_consensus_exiting.load(Ordering::Relaxed) {
+                if let Some(delay) = throttle_delay.take() {
                     drop(prune_guard);
-                    info!("Header and Block pruning interrupted: Process is exiting");
-                    return;
+                    std::thread::sleep(delay);
+                    io_throttle.checkpoint();
+                    prune_guard = self.pruning_lock.blocking_write();
+                } else {
+                    prune_guard.blocking_yield();
                 }
-                prune_guard.blocking_yield();
                 lock_acquire_time = Instant::now();
                 reachability_read = self.reachability_store.upgradable_read();
             }
 
-            if traversed % 1000 == 0 {
-                info!("Header and Block pruning: traversed: {}, pruned {}...", traversed, counter);
-            }
-
-            // Remove window cache entries
-            self.block_window_cache_for_difficulty.remove(&current);
-            self.block_window_cache_for_past_median_time.remove(&current);
-
-            if !keep_blocks.contains(&current) {
-                let mut batch = WriteBatch::default();
-                let mut level_relations_write = self.relations_stores.write();
-                let mut reachability_relations_write = self.reachability_relations_store.write();
-                let mut staging_relations = StagingRelationsStore::new(&mut reachability_relations_write);
-                let mut staging_reachability = StagingReachabilityStore::new(reachability_read);
-                let mut statuses_write = self.statuses_store.write();
-
-                // Prune data related to block bodies and UTXO state
-                self.utxo_multisets_store.delete_batch(&mut batch, current).unwrap();
-                self.utxo_diffs_store.delete_batch(&mut batch, current).unwrap();
-                self.acceptance_data_store.delete_batch(&mut batch, current).unwrap();
-                self.block_transactions_store.delete_batch(&mut batch, current).unwrap();
-
-                if let Some(&affiliated_proof_level) = keep_relations.get(&current) {
-                    if statuses_write.get(current).unwrap_option().is_some_and(|s| s.is_valid()) {
-                        // We set the status to header-only only if it was previously set to a valid
-                        // status. This is important since some proof headers might not have their status set
-                        // and we would like to preserve this semantic (having a valid status implies that
-                        // other parts of the code assume the existence of GD data etc.)
-                        statuses_write.set_batch(&mut batch, current, StatusHeaderOnly).unwrap();
-                    }
-
-                    // Delete level-x relations for blocks which only belong to higher-than-x proof levels.
-                    // This preserves the semantic that for each level, relations represent a contiguous DAG area in that level
-                    for lower_level in 0..affiliated_proof_level as usize {
-                        let mut staging_level_relations = StagingRelationsStore::new(&mut level_relations_write[lower_level]);
-                        relations::delete_level_relations(MemoryWriter, &mut staging_level_relations, current).unwrap_option();
-                        staging_level_relations.commit(&mut batch).unwrap();
-
-                        if lower_level == 0 {
-                            self.ghostdag_store.delete_batch(&mut batch, current).unwrap_option();
+            // Deletions of multiple blocks are accumulated into a single batch. All relations and reachability
+            // changes are staged for the whole batch and are committed at the same flush boundary
+            let mut batch = WriteBatch::default();
+            let mut level_relations_write = self.relations_stores.write();
+            let mut staging_level_relations = level_relations_write.iter_mut().map(StagingRelationsStore::new).collect_vec();
+            let mut reachability_relations_write = self.reachability_relations_store.write();
+            let mut staging_relations = StagingRelationsStore::new(&mut reachability_relations_write);
+            let mut staging_reachability = StagingReachabilityStore::new(reachability_read);
+            let mut statuses_write = self.statuses_store.write();
+            // Blocks fully pruned by this batch are collected only if there's an observer to report them to
+            let mut batch_pruned = self.observer.get().map(|_| Vec::new());
+            // Blocks in the pruning point future are only checked and skipped, so they are never recorded as pruned
+            let mut batch_last_pruned = None;
+            let mut stores = StagedPruningStores {
+                storage: &self.storage,
+                batch: &mut batch,
+                staging_level_relations: &mut staging_level_relations,
+                staging_relations: &mut staging_relations,
+                staging_reachability: &mut staging_reachability,
+                statuses_write: &mut statuses_write,
+                children_cache: &mut children_cache,
+                stats: &mut stats,
+            };
+
+            visit_batch(
+                &mut stores,
+                &plan,
+                new_pruning_point,
+                &mut queue,
+                // Flush once the batch reaches the configured maximal number of blocks, once it groups enough blocks and exceeds
+                // the configured size, or if it's time to yield the lock
+                |stores, batch_visited| {
+                    self.config.pruning_max_blocks_per_commit.is_some_and(|max| batch_visited >= max)
+                        || (batch_visited >= self.config.pruning_min_blocks_per_commit
+                            && !stores.batch.is_empty()
+                            && stores.batch.size_in_bytes() >= self.config.pruning_batch_flush_threshold)
+                        || lock_acquire_time.elapsed() > Duration::from_millis(5)
+                },
+                |current, outcome| {
+                    match outcome {
+                        TraversalOutcome::Skipped => return,
+                        // Count only blocks which get fully pruned including DAG relations
+                        TraversalOutcome::Pruned => {
+                            counter += 1;
+                            if let Some(batch_pruned) = batch_pruned.as_mut() {
+                                batch_pruned.push(current);
+                            }
                         }
+                        TraversalOutcome::Kept | TraversalOutcome::HeaderOnly => {}
                     }
-                } else {
-                    // Count only blocks which get fully pruned including DAG relations
-                    counter += 1;
-                    // Prune data related to headers: relations, reachability, ghostdag
-                    let mergeset = relations::delete_reachability_relations(
-                        MemoryWriter, // Both stores are staging so we just pass a dummy writer
-                        &mut staging_relations,
-                        &staging_reachability,
-                        current,
-                    );
-                    reachability::delete_block(&mut staging_reachability, current, &mut mergeset.iter().copied()).unwrap();
-                    // TODO: consider adding block level to compact header data
-                    let block_level = self.headers_store.get_header_with_block_level(current).unwrap().block_level;
-                    (0..=block_level as usize).for_each(|level| {
-                        let mut staging_level_relations = StagingRelationsStore::new(&mut level_relations_write[level]);
-                        relations::delete_level_relations(MemoryWriter, &mut staging_level_relations, current).unwrap_option();
-                        staging_level_relations.commit(&mut batch).unwrap();
-                    });
-
-                    self.ghostdag_store.delete_batch(&mut batch, current).unwrap_option();
-
-                    // Remove additional header related data
-                    self.daa_excluded_store.delete_batch(&mut batch, current).unwrap();
-                    self.depth_store.delete_batch(&mut batch, current).unwrap();
-                    // Remove status completely
-                    statuses_write.delete_batch(&mut batch, current).unwrap();
-
-                    if !keep_headers.contains(&current) {
-                        // Prune the actual headers
-                        self.headers_store.delete_batch(&mut batch, current).unwrap();
+                    batch_last_pruned = Some(current);
+                    traversed += 1;
+
+                    let interval = self.config.pruning_progress_log_interval;
+                    if (interval > 0 && traversed - last_progress_log.0 >= interval)
+                        || self.config.pruning_progress_log_period.is_some_and(|period| last_progress_log.1.elapsed() >= period)
+                    {
+                        info!("Header and Block pruning: traversed: {}, pruned {}...", traversed, counter);
+                        event!(Level::INFO, %new_pruning_point, traversed, pruned = counter, "pruning traversal progress");
+                        last_progress_log = (traversed, Instant::now());
                     }
-                }
+                },
+            );
+            drop(stores);
 
-                let reachability_write = staging_reachability.commit(&mut batch).unwrap();
-                staging_relations.commit(&mut batch).unwrap();
+            if let Some(last_pruned) = batch_last_pruned {
+                // The progress marker is written in the same batch, hence it always reflects fully committed batches
+                self.pruning_point_store.write().set_last_pruned(&mut batch, last_pruned).unwrap();
+            }
 
-                // Flush the batch to the DB
-                self.db.write(batch).unwrap();
+            // With background writers, the staged changes are committed into a batch of their own, so that the direct changes
+            // can be offloaded (see below). Otherwise all changes are written atomically as a single batch
+            let mut staging_batch = WriteBatch::default();
+            let commit_batch = if batch_writer.is_some() { &mut staging_batch } else { &mut batch };
+            let commit_start = Instant::now();
+            // The cache reads through the staging store, hence it is scoped to the batch (see `ChildrenCache`)
+            children_cache.clear();
+            for staging_level_relations in staging_level_relations.iter_mut() {
+                staging_level_relations.commit(commit_batch).unwrap();
+            }
+            let reachability_write = staging_reachability.commit(commit_batch).unwrap();
+            staging_relations.commit(commit_batch).unwrap();
+            stats.staging_commits_duration += commit_start.elapsed();
+
+            // Flush the batches to the DB
+            let batch_size = batch.size_in_bytes() + staging_batch.size_in_bytes();
+            let write_start = Instant::now();
+            match batch_writer.as_mut() {
+                // Staged relations and reachability changes might rewrite keys of previous commits, so they are written
+                // synchronously and in order. They are written first, since a block whose data was deleted must no longer be
+                // reachable through the DAG relations. The direct changes only delete data of pruned blocks or write per-block
+                // entries (statuses, retention markers) at most once per traversal, so they have no ordering constraints and
+                // are written in the background. An interrupted run might hence leave unreachable data of pruned blocks behind,
+                // which is collected by the orphan sweep (always enabled in this mode, see `Config::pruning_db_writer_threads`).
+                // Note that the last pruned marker might also be committed out of order, which is harmless since it is only a
+                // diagnostic hint (an interrupted prune is resumed by traversing from origin regardless)
+                Some(batch_writer) => {
+                    if !staging_batch.is_empty() {
+                        self.write_committed_batch_or_panic(staging_batch);
+                    }
+                    batch_writer.submit(batch);
+                    stats.offloaded_batches += 1;
+                }
+                None => self.write_committed_batch_or_panic(batch),
+            }
+            stats.db_writes_duration += write_start.elapsed();
+            throttle_delay = io_throttle.record(batch_size);
+            if let (Some(observer), Some(batch_pruned)) = (self.observer.get(), batch_pruned) {
+                if !batch_pruned.is_empty() {
+                    observer.on_blocks_pruned(&batch_pruned);
+                }
+            }
 
-                // Calling the drops explicitly after the batch is written in order to avoid possible errors.
-                drop(reachability_write);
-                drop(statuses_write);
-                drop(reachability_relations_write);
-                drop(level_relations_write);
+            // Calling the drops explicitly after the batch is written in order to avoid possible errors.
+            drop(reachability_write);
+            drop(statuses_write);
+            drop(reachability_relations_write);
+            drop(staging_level_relations);
+            drop(level_relations_write);
+
+            // An exit signal was received. Exit from this long running process. Exiting is checked only at batch boundaries,
+            // i.e., once all the batch changes (including staged reachability and relations changes) were written, so that a
+            // batch is never partially applied. The recovery workflow restarts the traversal on the next run
+            if !queue.is_empty() && self.is_consensus_exiting.load(Ordering::Relaxed) {
+                Self::flush_batch_writer_or_panic(batch_writer.as_mut());
+                drop(prune_guard);
+                info!("Header and Block pruning interrupted: Process is exiting");
+                return Ok(PruningReport::default());
+            }
 
-                reachability_read = self.reachability_store.upgradable_read();
+            // The soft deadline was exceeded. Similarly to exiting, we stop at a batch boundary, where the last pruned marker
+            // reflects all committed progress. The queued frontier is persisted and the prune is marked as deferred, so that the
+            // next processing message resumes the traversal from where it stopped (see `DbPruningQueueStore`)
+            if !queue.is_empty() && self.config.pruning_soft_deadline.is_some_and(|deadline| traversal_start.elapsed() > deadline) {
+                Self::flush_batch_writer_or_panic(batch_writer.as_mut());
+                drop(prune_guard);
+                let (start, end) = queue.persist();
+                let mut frontier_store = DbPruningQueueStore::new(self.db.clone());
+                frontier_store
+                    .set_deferred_frontier(DeferredTraversalFrontier { pruning_point: new_pruning_point, start, end })
+                    .unwrap();
+                self.has_deferred_prune.store(true, Ordering::Relaxed);
+                info!(
+                    "Header and Block pruning reached its soft deadline after {:.2?}: traversed {}, pruned {}; deferring the remaining work ({} blocks queued, more may follow)",
+                    traversal_start.elapsed(),
+                    traversed,
+                    counter,
+                    end - start
+                );
+                // The partial stats are reported to the caller, while the stats history only records completed runs
+                stats.traversal_duration = traversal_start.elapsed();
+                stats.traversed = traversed;
+                stats.pruned = counter;
+                stats.total_duration = prune_start.elapsed();
+                return Ok(PruningReport { completed: false, stats });
             }
+
+            reachability_read = self.reachability_store.upgradable_read();
         }
 
+        // All traversal deletions must be committed before the history root is advanced below
+        Self::flush_batch_writer_or_panic(batch_writer.as_mut());
+        drop(batch_writer);
         drop(reachability_read);
         drop(prune_guard);
+        stats.traversal_duration = traversal_start.elapsed();
+        stats.traversed = traversed;
+        stats.pruned = counter;
+        traversal_span.record("traversed", traversed).record("pruned", counter);
+        drop(traversal_span);
+        if queue.spilled_count() > 0 {
+            info!("Header and Block pruning: {} queued blocks were spilled to the DB during traversal", queue.spilled_count());
+        }
+
+        self.expire_retained_acceptance_data(plan.acceptance_data_cutoff);
+
+        if self.config.effective_pruning_orphan_sweep() {
+            self.sweep_orphans(&plan, new_pruning_point, &mut stats);
+        }
 
-        info!("Header and Block pruning completed: traversed: {}, pruned {}", traversed, counter);
+        info!(
+            "Header and Block pruning completed: traversed: {}, pruned {}, orphans collected: {}, in {:.2?} ({:.0} blocks/s)",
+            stats.traversed,
+            stats.pruned,
+            stats.orphans,
+            stats.traversal_duration,
+            stats.traversal_throughput()
+        );
+        info!(
+            "Header and Block pruning durations: plan: {:.2?}, ghostdag updates: {:.2?}, tips and chain: {:.2?}, body deletes: {:.2?}, header deletes: {:.2?}, staging commits: {:.2?}, db writes: {:.2?}",
+            stats.plan_duration,
+            stats.ghostdag_updates_duration,
+            stats.tips_and_chain_duration,
+            stats.body_deletes_duration,
+            stats.header_deletes_duration,
+            stats.staging_commits_duration,
+            stats.db_writes_duration
+        );
+        info!(
+            "Header and Block pruning reachability children lookups: {}, served from cache: {} (store reads avoided)",
+            children_cache.lookups(),
+            children_cache.hits()
+        );
+        info!(
+            "Header and Block pruning IO: written {} bytes, average throughput: {:.2} MB/s, batches written in the background: {}",
+            io_throttle.total_bytes(),
+            io_throttle.average_throughput() / 1_000_000.0,
+            stats.offloaded_batches
+        );
         info!(
             "Header and Block pruning stats: proof size: {}, pruning point and anticone: {}, unique headers in proof and windows: {}, pruning points in history: {}",
             proof.iter().map(|l| l.len()).sum::<usize>(),
-            keep_blocks.len(),
-            keep_relations.len(),
-            keep_headers.len()
+            plan.keep_blocks.len(),
+            plan.keep_relations.len(),
+            past_pruning_points_count
         );
 
-        if self.config.enable_sanity_checks {
+        let sanity_checks = self.config.effective_pruning_sanity_checks();
+        if sanity_checks.proof_rebuild {
             self.assert_proof_rebuilding(proof, new_pruning_point);
+        }
+        if sanity_checks.trusted_data_rebuild {
             self.assert_data_rebuilding(data, new_pruning_point);
         }
+        if sanity_checks.pruned_statuses {
+            if let Err(err) = self.verify_pruned_statuses(&plan.keep_relations, &plan.keep_blocks) {
+                panic!("Header and Block pruning: {}", err);
+            }
+        }
+        if sanity_checks.dangling_relations {
+            if let Err(err) = self.verify_relations_no_dangling(&plan.keep_relations) {
+                panic!("Header and Block pruning: {}", err);
+            }
+        }
 
         {
             // Set the history root to the new pruning point only after we successfully pruned its past
             let mut pruning_point_write = self.pruning_point_store.write();
             let mut batch = WriteBatch::default();
             pruning_point_write.set_history_root(&mut batch, new_pruning_point).unwrap();
-            self.db.write(batch).unwrap();
+            pruning_point_write.clear_last_pruned(&mut batch).unwrap();
+            self.write_batch_with_retry(batch)?;
             drop(pruning_point_write);
         }
+
+        stats.total_duration = prune_start.elapsed();
+        stats.timestamp = unix_now();
+        // The stats history is diagnostic only, hence failing to record it does not fail the (already completed) prune
+        let mut batch = WriteBatch::default();
+        match self.pruning_stats_history_store.push_batch(&mut batch, stats.clone(), self.config.pruning_stats_history_length) {
+            Ok(()) => {
+                if let Err(err) = self.write_batch_with_retry(batch) {
+                    warn!("Header and Block pruning: failed recording the pruning stats: {}", err);
+                }
+            }
+            Err(err) => warn!("Header and Block pruning: failed recording the pruning stats: {}", err),
+        }
+
+        if self.config.pruning_post_compaction {
+            self.compact_pruned_stores();
+        }
+        Ok(PruningReport { completed: true, stats })
     }
 
-    fn past_pruning_points(&self) -> BlockHashSet {
-        (0..self.pruning_point_store.read().get().unwrap().index)
-            .map(|index| self.past_pruning_points_store.get(index).unwrap())
-            .collect()
+    /// Returns whether data of `hash` was removed by pruning, allowing callers to tell pruned blocks from unknown ones:
+    /// - Blocks retained as part of the DAG structure (e.g., kept windows) are reported as pruned if they are in the strict
+    ///   past of the pruning point and their body was pruned (as reflected by a header-only status)
+    /// - Blocks pruned entirely are reported as pruned if their header was retained, i.e., for past pruning points or when
+    ///   `Config::retain_all_headers` is set. Otherwise a fully pruned block leaves no trace and is indistinguishable from an
+    ///   unknown block, for which `false` is returned
+    ///
+    /// Archival nodes never prune data, hence always return `false`
+    pub fn is_pruned(&self, hash: Hash) -> bool {
+        if self.config.is_archival {
+            return false;
+        }
+        let pruning_point = self.pruning_point_store.read().pruning_point().unwrap();
+        let reachability_read = self.reachability_store.read();
+        if reachability_read.has(hash).unwrap() {
+            if hash == pruning_point || !reachability_read.is_dag_ancestor_of_result(hash, pruning_point).unwrap() {
+                return false;
+            }
+            drop(reachability_read);
+            return self.statuses_store.read().get(hash).unwrap_option().is_some_and(|status| status == StatusHeaderOnly);
+        }
+        drop(reachability_read);
+        match self.headers_store.get_blue_score(hash).unwrap_option() {
+            Some(blue_score) => blue_score < self.headers_store.get_blue_score(pruning_point).unwrap(),
+            None => false,
+        }
+    }
+
+    /// Returns the statistics of the recent data pruning runs (up to `Config::pruning_stats_history_length`), oldest first
+    pub fn recent_pruning_stats(&self) -> Vec<PruningStats> {
+        self.pruning_stats_history_store.get_all()
+    }
+
+    /// Manually compacts the key ranges of the stores most heavily deleted from by pruning, so that disk space is reclaimed
+    /// without waiting for background compactions. Runs with no locks held, since compaction does not affect the logical
+    /// DB state (it can however be IO-heavy, see `Config::pruning_post_compaction`)
+    fn compact_pruned_stores(&self) {
+        if self.is_consensus_exiting.load(Ordering::Relaxed) {
+            return;
+        }
+
+        info!("Header and Block pruning: compacting pruned stores...");
+        let start = Instant::now();
+        let size_before = self.db.total_sst_files_size();
+
+        self.block_transactions_store.compact();
+        self.acceptance_data_store.compact();
+        self.utxo_diffs_store.compact();
+        self.headers_store.compact();
+        // Compact through a cache-less handle so that the reachability lock is not held while compacting
+        let reachability_store = self.reachability_store.read().clone_with_new_cache(CachePolicy::Empty, CachePolicy::Empty);
+        reachability_store.compact();
+
+        match (size_before, self.db.total_sst_files_size()) {
+            (Some(before), Some(after)) => info!(
+                "Header and Block pruning: compaction completed in {:.2?}, reclaimed {} bytes ({} bytes remaining)",
+                start.elapsed(),
+                before.saturating_sub(after),
+                after
+            ),
+            _ => info!("Header and Block pruning: compaction completed in {:.2?}", start.elapsed()),
+        }
+    }
+
+    /// Deletes the retained acceptance data of pruned blocks with a blue score lower than `cutoff`. If acceptance data retention
+    /// is disabled, all retained data is deleted (e.g., if retention was enabled on a former run)
+    fn expire_retained_acceptance_data(&self, cutoff: Option<u64>) {
+        let expired = self.acceptance_retention_store.expired(cutoff.unwrap_or(u64::MAX)).unwrap();
+        if expired.is_empty() {
+            return;
+        }
+        let mut batch = WriteBatch::default();
+        for &hash in expired.iter() {
+            self.acceptance_data_store.delete_batch(&mut batch, hash).unwrap();
+            self.acceptance_retention_store.delete_batch(&mut batch, hash).unwrap();
+        }
+        // The deletions were already applied to the store caches
+        self.write_committed_batch_or_panic(batch);
+        info!("Header and Block pruning: deleted the retained acceptance data of {} blocks", expired.len());
+    }
+
+    /// Sweeps the statuses store for orphaned blocks, i.e., blocks below `new_pruning_point` which are not reachable from ORIGIN
+    /// via the reachability tree and were hence not visited by the pruning traversal. Since the traversal deletes the status of
+    /// every visited block it does not keep, any remaining status of a block outside the plan which is not in the future of the
+    /// pruning point belongs to such an orphan. The number of orphans collected is recorded in `stats`
+    fn sweep_orphans(&self, plan: &PruningPlan, new_pruning_point: Hash, stats: &mut PruningStats) {
+        if self.is_consensus_exiting.load(Ordering::Relaxed) {
+            return;
+        }
+
+        info!("Header and Block pruning: sweeping for orphaned blocks...");
+
+        // Candidates are scanned with no locks held, and are re-validated below once the locks are acquired
+        let pruning_point_blue_score = self.headers_store.get_blue_score(new_pruning_point).unwrap();
+        let is_orphan = |reachability_store: &DbReachabilityStore, hash: Hash| {
+            if plan.keep_blocks.contains(&hash) || plan.keep_relations.contains_key(&hash) {
+                return false;
+            }
+            if reachability_store.has(hash).unwrap() {
+                !reachability_store.is_dag_ancestor_of_result(new_pruning_point, hash).unwrap()
+                    && !reachability::is_reachable_from_origin(reachability_store, hash).unwrap()
+            } else {
+                // Without reachability data we fall back to comparing blue scores with the pruning point
+                self.headers_store.get_blue_score(hash).unwrap_option().is_none_or(|blue_score| blue_score < pruning_point_blue_score)
+            }
+        };
+        let statuses = self.statuses_store.read().clone();
+        let candidates = statuses
+            .iterator()
+            .map(|res| res.unwrap().0)
+            .filter(|&hash| is_orphan(&self.reachability_store.read(), hash))
+            .collect_vec();
+
+        if candidates.is_empty() {
+            return;
+        }
+
+        let prune_guard = self.pruning_lock.blocking_write();
+        let reachability_read = self.reachability_store.upgradable_read();
+        // The scan might have raced with block processing or with a concurrent pruning step, hence each candidate is checked
+        // again under the locks, where its status and reachability data can no longer change
+        let statuses_read = self.statuses_store.read();
+        let orphans = candidates
+            .into_iter()
+            .filter(|&hash| statuses_read.has(hash).unwrap() && is_orphan(&reachability_read, hash))
+            .collect_vec();
+        drop(statuses_read);
+        if orphans.is_empty() {
+            return;
+        }
+
+        let mut batch = WriteBatch::default();
+        let mut level_relations_write = self.relations_stores.write();
+        let mut staging_level_relations = level_relations_write.iter_mut().map(StagingRelationsStore::new).collect_vec();
+        let mut reachability_relations_write = self.reachability_relations_store.write();
+        let mut staging_relations = StagingRelationsStore::new(&mut reachability_relations_write);
+        let mut staging_reachability = StagingReachabilityStore::new(reachability_read);
+        let mut statuses_write = self.statuses_store.write();
+        let mut children_cache = ChildrenCache::new(0);
+        let mut stores = StagedPruningStores {
+            storage: &self.storage,
+            batch: &mut batch,
+            staging_level_relations: &mut staging_level_relations,
+            staging_relations: &mut staging_relations,
+            staging_reachability: &mut staging_reachability,
+            statuses_write: &mut statuses_write,
+            children_cache: &mut children_cache,
+            stats: &mut *stats,
+        };
+        for &orphan in orphans.iter() {
+            let keep_header = stores.is_past_pruning_point(orphan);
+            stores.delete_orphan_data(orphan, keep_header);
+        }
+        drop(stores);
+
+        for staging_level_relations in staging_level_relations.iter_mut() {
+            staging_level_relations.commit(&mut batch).unwrap();
+        }
+        let reachability_write = staging_reachability.commit(&mut batch).unwrap();
+        staging_relations.commit(&mut batch).unwrap();
+        self.write_committed_batch_or_panic(batch);
+
+        drop(reachability_write);
+        drop(statuses_write);
+        drop(reachability_relations_write);
+        drop(staging_level_relations);
+        drop(level_relations_write);
+        drop(prune_guard);
+
+        warn!(
+            "Header and Block pruning: collected {} orphaned blocks: {}",
+            orphans.len(),
+            orphans.iter().take(5).reusable_format(", ")
+        );
+        stats.orphans = orphans.len() as u64;
+    }
+
+    /// Verifies that the GHOSTDAG data of blocks retaining level-0 relations only refers to retained blocks, i.e., that the
+    /// selected parent of each such block is either retained or ORIGIN, and that all its mergeset members are retained.
+    /// This is the invariant maintained by the GHOSTDAG rewrite performed during pruning, and is far cheaper to check than
+    /// a full proof rebuild. Blocks are checked in sorted order, so the reported violation is deterministic
+    pub fn verify_keep_relations_closure(&self, keep_relations: &BlockHashMap<BlockLevel>) -> PruningVerificationResult<()> {
+        let is_retained = |hash: Hash| keep_relations.get(&hash) == Some(&0);
+        for block in keep_relations.iter().filter(|(_, &level)| level == 0).map(|(&hash, _)| hash).sorted() {
+            let Some(ghostdag) = self.ghostdag_store.get_data(block).unwrap_option() else {
+                continue;
+            };
+            if ghostdag.selected_parent != ORIGIN && !is_retained(ghostdag.selected_parent) {
+                return Err(PruningVerificationError::RetainedSelectedParentMissing {
+                    block,
+                    selected_parent: ghostdag.selected_parent,
+                });
+            }
+            if let Some(member) = ghostdag.unordered_mergeset().find(|&h| !is_retained(h)) {
+                return Err(PruningVerificationError::RetainedMergesetMemberMissing { block, member });
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies that the level-x relations retained for each block in `keep_relations` (i.e., for blocks affiliated with a
+    /// level lower or equal to x) only refer to ORIGIN or to blocks whose level-x relations were retained as well. Such a
+    /// dangling edge indicates that a relations deletion missed updating a neighbour. Levels and blocks are checked in
+    /// sorted order, so the reported violation is deterministic
+    pub fn verify_relations_no_dangling(&self, keep_relations: &BlockHashMap<BlockLevel>) -> PruningVerificationResult<()> {
+        let relations_read = self.relations_stores.read();
+        for (level, relations) in relations_read.iter().enumerate() {
+            let level = level as BlockLevel;
+            for block in keep_relations.iter().filter(|(_, &affiliated)| affiliated <= level).map(|(&hash, _)| hash).sorted() {
+                // Relations at this level might not exist for the block to begin with (e.g., if its block level is lower)
+                let Some(parents) = relations.get_parents(block).unwrap_option() else {
+                    continue;
+                };
+                let children = relations.get_children(block).unwrap();
+                let children = children.read().iter().copied().sorted().collect_vec();
+                for referenced in parents.iter().copied().filter(|&h| h != ORIGIN).chain(children) {
+                    if !relations.has(referenced).unwrap() {
+                        return Err(PruningVerificationError::DanglingRelation { block, level, referenced });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies the statuses left by pruning the past of the pruning point:
+    ///
+    /// 1. Blocks retaining relations (`keep_relations`) but not full data (`keep_blocks`) are header-only. A missing status is
+    ///    tolerated since such blocks might be proof headers whose status was never set (see `demote_to_header_only`)
+    /// 2. Past pruning points which were fully pruned (keeping only their header) have no status left. This holds when all
+    ///    headers are retained as well (see `Config::retain_all_headers`)
+    ///
+    /// Blocks are checked in sorted order, so the reported violation is deterministic
+    pub fn verify_pruned_statuses(
+        &self,
+        keep_relations: &BlockHashMap<BlockLevel>,
+        keep_blocks: &BlockHashSet,
+    ) -> PruningVerificationResult<()> {
+        let statuses_read = self.statuses_store.read();
+        let demoted = keep_relations.keys().copied().filter(|hash| !keep_blocks.contains(hash)).sorted();
+        for block in demoted {
+            if let Some(status) = statuses_read.get(block).unwrap_option().filter(|&status| status != StatusHeaderOnly) {
+                return Err(PruningVerificationError::DemotedBlockStatusMismatch { block, status });
+            }
+        }
+
+        let pruned_past_pruning_points = self
+            .past_pruning_points_store
+            .iter()
+            .map(|(_, hash)| hash)
+            .filter(|hash| !keep_relations.contains_key(hash) && !keep_blocks.contains(hash))
+            .sorted();
+        for block in pruned_past_pruning_points {
+            if let Some(status) = statuses_read.get(block).unwrap_option() {
+                return Err(PruningVerificationError::PrunedBlockStatusLeft { block, status });
+            }
+        }
+        Ok(())
     }
 
     fn assert_proof_rebuilding(&self, ref_proof: Arc<PruningPointProof>, new_pruning_point: Hash) {
         info!("Rebuilding the pruning proof after pruning data (sanity test)");
+        if let Err(err) = self.verify_proof_rebuilding(&ref_proof, new_pruning_point) {
+            panic!("{}", err);
+        }
+        info!("Proof was rebuilt successfully following pruning");
+    }
+
+    /// Rebuilds the pruning point proof from the current (pruned) data and verifies it matches `ref_proof`
+    pub fn verify_proof_rebuilding(&self, ref_proof: &PruningPointProof, new_pruning_point: Hash) -> PruningVerificationResult<()> {
         let proof_hashes = ref_proof.iter().flatten().map(|h| h.hash).collect::<Vec<_>>();
         let built_proof = self.pruning_proof_manager.build_pruning_point_proof(new_pruning_point);
         let built_proof_hashes = built_proof.iter().flatten().map(|h| h.hash).collect::<Vec<_>>();
-        assert_eq!(proof_hashes.len(), built_proof_hashes.len(), "Rebuilt proof does not match the expected reference");
-        for (i, (a, b)) in proof_hashes.into_iter().zip(built_proof_hashes).enumerate() {
-            if a != b {
-                panic!("Proof built following pruning does not match the previous proof: built[{}]={}, prev[{}]={}", i, b, i, a);
+        if proof_hashes.len() != built_proof_hashes.len() {
+            return Err(PruningVerificationError::ProofLengthMismatch(proof_hashes.len(), built_proof_hashes.len()));
+        }
+        for (index, (expected, built)) in proof_hashes.into_iter().zip(built_proof_hashes).enumerate() {
+            if expected != built {
+                return Err(PruningVerificationError::ProofHeaderMismatch { index, expected, built });
             }
         }
-        info!("Proof was rebuilt successfully following pruning");
+        Ok(())
     }
 
     fn assert_data_rebuilding(&self, ref_data: Arc<PruningPointTrustedData>, new_pruning_point: Hash) {
         info!("Rebuilding pruning point trusted data (sanity test)");
+        if let Err(err) = self.verify_data_rebuilding(&ref_data, new_pruning_point) {
+            panic!("{}", err);
+        }
+        info!("Trusted data was rebuilt successfully following pruning");
+    }
+
+    /// Rebuilds the pruning point anticone and trusted data from the current (pruned) data and verifies it matches `ref_data`
+    pub fn verify_data_rebuilding(
+        &self,
+        ref_data: &PruningPointTrustedData,
+        new_pruning_point: Hash,
+    ) -> PruningVerificationResult<()> {
         let virtual_state = self.lkg_virtual_state.load();
         let built_data = self
             .pruning_proof_manager
             .calculate_pruning_point_anticone_and_trusted_data(new_pruning_point, virtual_state.parents.iter().copied());
-        assert_eq!(
-            ref_data.anticone.iter().copied().collect::<BlockHashSet>(),
-            built_data.anticone.iter().copied().collect::<BlockHashSet>()
-        );
-        assert_eq!(
-            ref_data.daa_window_blocks.iter().map(|th| th.header.hash).collect::<BlockHashSet>(),
-            built_data.daa_window_blocks.iter().map(|th| th.header.hash).collect::<BlockHashSet>()
-        );
-        assert_eq!(
-            ref_data.ghostdag_blocks.iter().map(|gd| gd.hash).collect::<BlockHashSet>(),
-            built_data.ghostdag_blocks.iter().map(|gd| gd.hash).collect::<BlockHashSet>()
-        );
-        info!("Trusted data was rebuilt successfully following pruning");
+        if ref_data.anticone.iter().copied().collect::<BlockHashSet>() != built_data.anticone.iter().copied().collect::<BlockHashSet>()
+        {
+            return Err(PruningVerificationError::AnticoneMismatch);
+        }
+        if ref_data.daa_window_blocks.iter().map(|th| th.header.hash).collect::<BlockHashSet>()
+            != built_data.daa_window_blocks.iter().map(|th| th.header.hash).collect::<BlockHashSet>()
+        {
+            return Err(PruningVerificationError::DaaWindowMismatch);
+        }
+        if ref_data.ghostdag_blocks.iter().map(|gd| gd.hash).collect::<BlockHashSet>()
+            != built_data.ghostdag_blocks.iter().map(|gd| gd.hash).collect::<BlockHashSet>()
+        {
+            return Err(PruningVerificationError::GhostdagBlocksMismatch);
+        }
+        Ok(())
     }
 }
+
+/// Returns whether the rewritten GHOSTDAG data of a retained block is coherent with `plan`: all mergeset members are
+/// retained, the selected parent (unless reassigned to ORIGIN) is still the first mergeset blue, and anticone sizes
+/// are recorded for retained blues only. Used for debug assertions of the GHOSTDAG rewrite performed by pruning
+fn is_coherent_ghostdag_rewrite(ghostdag: &ExternalGhostdagData, plan: &PruningPlan) -> bool {
+    let members_retained =
+        ghostdag.mergeset_blues.iter().chain(ghostdag.mergeset_reds.iter()).all(|&h| plan.keeps_level_zero_relations(h));
+    // The mergeset blues of a block start with its selected parent, unless the latter was pruned and reassigned to ORIGIN
+    let selected_parent_coherent =
+        ghostdag.selected_parent == ORIGIN || ghostdag.mergeset_blues.first() == Some(&ghostdag.selected_parent);
+    let anticone_sizes_coherent = ghostdag.blues_anticone_sizes.keys().all(|h| ghostdag.mergeset_blues.contains(h));
+    members_retained && selected_parent_coherent && anticone_sizes_coherent
+}
@@ -0,0 +1,12 @@
+use crate::model::stores::pruning_stats_history::PruningStats;
+
+/// The outcome of a single data pruning run, see `PruningProcessor::prune_with_report`
+#[derive(Debug, Default, Clone)]
+pub struct PruningReport {
+    /// Whether the run pruned all data in the past of the pruning point. A run might skip (archival node, pruning
+    /// point of insufficient depth) or stop on process exit, in which case the stats are empty, or stop at its soft
+    /// deadline, in which case the stats cover the deferred run so far. Only completed runs are recorded in the stats history
+    pub completed: bool,
+    /// Counts and per-phase durations of the run. The reachability commits phase is `staging_commits_duration`
+    pub stats: PruningStats,
+}
@@ -0,0 +1,682 @@
+use super::{
+    children_cache::ChildrenCache,
+    plan::PruningPlan,
+    traversal_queue::{QueueSpillStore, TraversalQueue},
+};
+use crate::{
+    consensus::storage::ConsensusStorage,
+    model::{
+        services::reachability::ReachabilityService,
+        stores::{
+            ghostdag::GhostdagStoreReader,
+            headers::HeaderStoreReader,
+            past_pruning_points::PastPruningPointsStoreReader,
+            pruning_stats_history::PruningStats,
+            reachability::{ReachabilityStore, ReachabilityStoreReader, StagingReachabilityStore},
+            relations::StagingRelationsStore,
+            statuses::DbStatusesStore,
+            statuses::StatusesStoreReader,
+        },
+    },
+    processes::{
+        reachability::inquirer::{self as reachability, AntifutureWalkStore},
+        relations,
+    },
+};
+use kaspa_consensus_core::{blockhash::BlockHashes, blockstatus::BlockStatus::StatusHeaderOnly, BlockLevel};
+use kaspa_database::prelude::{MemoryWriter, StoreResultExtensions};
+use kaspa_hashes::Hash;
+use rocksdb::WriteBatch;
+use std::time::Instant;
+
+/// The narrow store surface touched by the pruning traversal. The production implementation stages all changes
+/// into a single write batch (see [`StagedPruningStores`]), while tests can use a simple in-memory implementation. The
+/// traversal order is determined by the [`AntifutureWalkStore`] surface, shared with [`reachability::antifuture_iterator`]
+pub trait PruningTraversalStores: AntifutureWalkStore {
+    /// Returns whether `hash` is a past pruning point (whose header must be kept)
+    fn is_past_pruning_point(&self, hash: Hash) -> bool;
+
+    /// Evicts any cached in-memory data related to `hash`
+    fn evict_caches(&mut self, hash: Hash);
+
+    /// Returns the blue score of `hash`, if its header is available
+    fn blue_score(&self, hash: Hash) -> Option<u64>;
+
+    /// Deletes data related to the block body and UTXO state of `hash`. If `retained_acceptance_blue_score` is set, the
+    /// acceptance data of the block is retained and tracked by this blue score until it expires
+    fn delete_body_data(&mut self, hash: Hash, retained_acceptance_blue_score: Option<u64>);
+
+    /// Turns `hash` into a header-only block which keeps relations only from `affiliated_level` and up
+    fn demote_to_header_only(&mut self, hash: Hash, affiliated_level: BlockLevel);
+
+    /// Deletes all header related data of `hash` (relations, reachability, ghostdag, status etc.). The header
+    /// itself is deleted as well unless `keep_header` is set
+    fn delete_header_data(&mut self, hash: Hash, keep_header: bool);
+}
+
+/// The outcome of visiting a single block by the pruning traversal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraversalOutcome {
+    /// The block is in the future of the pruning point, hence it and its subtree are not traversed. Only reported for blocks
+    /// queued by the caller, since children in the pruning point future are never queued by [`visit_block`]
+    Skipped,
+    /// The block is in the pruning point anticone and is fully kept
+    Kept,
+    /// Body data was pruned while (some of) the block relations are kept
+    HeaderOnly,
+    /// The block was fully pruned (keeping only the header for past pruning points)
+    Pruned,
+}
+
+/// Visits `current` according to `plan`: pushes its reachability tree children which are not in the future of
+/// `new_pruning_point` to `queue` (unless the block itself is in that future) and deletes the block data which is not
+/// required to be kept
+pub fn visit_block(
+    stores: &mut (impl PruningTraversalStores + ?Sized),
+    plan: &PruningPlan,
+    new_pruning_point: Hash,
+    current: Hash,
+    queue: &mut impl Extend<Hash>,
+) -> TraversalOutcome {
+    if stores.is_in_future_of(new_pruning_point, current) {
+        return TraversalOutcome::Skipped;
+    }
+
+    // Push the tree children of `current` to the queue before possibly being deleted below. Children in the future of the
+    // pruning point are filtered out right away rather than being queued (and possibly spilled) only to be skipped when visited
+    reachability::extend_antifuture_walk(stores, current, new_pruning_point, queue);
+
+    let is_kept = plan.keep_blocks.contains(&current);
+    if !is_kept || !plan.retain_kept_window_caches {
+        stores.evict_caches(current);
+    }
+
+    if is_kept {
+        return TraversalOutcome::Kept;
+    }
+
+    // Acceptance data of blocks within the retention depth is retained (see `Config::acceptance_data_retention`)
+    let retained_acceptance_blue_score =
+        plan.acceptance_data_cutoff.and_then(|cutoff| stores.blue_score(current).filter(|&blue_score| blue_score >= cutoff));
+    stores.delete_body_data(current, retained_acceptance_blue_score);
+
+    if let Some(&affiliated_proof_level) = plan.keep_relations.get(&current) {
+        stores.demote_to_header_only(current, affiliated_proof_level);
+        TraversalOutcome::HeaderOnly
+    } else if plan.retain_all_headers {
+        // Similar to past pruning points, the retained header is left with no status, so that the block is not mistaken for
+        // one whose GHOSTDAG, reachability or relations data exists (e.g., by `validate_block_exists` or body processing)
+        stores.delete_header_data(current, true);
+        TraversalOutcome::Pruned
+    } else {
+        let keep_header = stores.is_past_pruning_point(current);
+        stores.delete_header_data(current, keep_header);
+        TraversalOutcome::Pruned
+    }
+}
+
+/// Visits queued blocks until the queue drains or `should_flush` signals a batch boundary (given the stores and the number
+/// of blocks visited by the batch so far). `on_visit` is called with the outcome of each visited block. Returns the last
+/// visited block, if any.
+///
+/// The caller is expected to commit all changes staged by the batch atomically, and to check for cancellation only in
+/// between batches, so that a batch is never partially applied
+pub fn visit_batch<S: PruningTraversalStores, Q: QueueSpillStore>(
+    stores: &mut S,
+    plan: &PruningPlan,
+    new_pruning_point: Hash,
+    queue: &mut TraversalQueue<Q>,
+    mut should_flush: impl FnMut(&S, usize) -> bool,
+    mut on_visit: impl FnMut(Hash, TraversalOutcome),
+) -> Option<Hash> {
+    let mut last_visited = None;
+    let mut batch_visited = 0;
+    while let Some(current) = queue.front() {
+        if batch_visited > 0 && should_flush(stores, batch_visited) {
+            break;
+        }
+        queue.pop_front();
+        batch_visited += 1;
+        on_visit(current, visit_block(stores, plan, new_pruning_point, current, queue));
+        last_visited = Some(current);
+    }
+    last_visited
+}
+
+/// The production implementation of [`PruningTraversalStores`], staging all deletions into a single write batch
+/// and staging relations and reachability changes until committed by the caller
+pub(super) struct StagedPruningStores<'a, 'b> {
+    pub storage: &'a ConsensusStorage,
+    pub batch: &'a mut WriteBatch,
+    pub staging_level_relations: &'a mut [StagingRelationsStore<'b>],
+    pub staging_relations: &'a mut StagingRelationsStore<'b>,
+    pub staging_reachability: &'a mut StagingReachabilityStore<'b>,
+    pub statuses_write: &'a mut DbStatusesStore,
+    pub children_cache: &'a mut ChildrenCache,
+    pub stats: &'a mut PruningStats,
+}
+
+impl AntifutureWalkStore for StagedPruningStores<'_, '_> {
+    fn is_in_future_of(&self, pruning_point: Hash, hash: Hash) -> bool {
+        self.staging_reachability.is_dag_ancestor_of_result(pruning_point, hash).unwrap()
+    }
+
+    fn tree_children(&mut self, hash: Hash) -> BlockHashes {
+        self.children_cache.get_children(&*self.staging_reachability, hash).unwrap()
+    }
+}
+
+impl PruningTraversalStores for StagedPruningStores<'_, '_> {
+    fn is_past_pruning_point(&self, hash: Hash) -> bool {
+        self.storage.past_pruning_points_store.contains(hash).unwrap()
+    }
+
+    fn evict_caches(&mut self, hash: Hash) {
+        // Remove window cache entries
+        self.storage.block_window_cache_for_difficulty.remove(&hash);
+        self.storage.block_window_cache_for_past_median_time.remove(&hash);
+    }
+
+    fn blue_score(&self, hash: Hash) -> Option<u64> {
+        self.storage.headers_store.get_blue_score(hash).unwrap_option()
+    }
+
+    fn delete_body_data(&mut self, hash: Hash, retained_acceptance_blue_score: Option<u64>) {
+        let start = Instant::now();
+        // Prune data related to block bodies and UTXO state
+        self.storage.utxo_multisets_store.delete_batch(self.batch, hash).unwrap();
+        self.storage.utxo_diffs_store.delete_batch(self.batch, hash).unwrap();
+        match retained_acceptance_blue_score {
+            Some(blue_score) => self.storage.acceptance_retention_store.insert_batch(self.batch, hash, blue_score).unwrap(),
+            None => self.storage.acceptance_data_store.delete_batch(self.batch, hash).unwrap(),
+        }
+        self.storage.block_transactions_store.delete_batch(self.batch, hash).unwrap();
+        self.stats.body_deletes_duration += start.elapsed();
+    }
+
+    fn demote_to_header_only(&mut self, hash: Hash, affiliated_level: BlockLevel) {
+        let start = Instant::now();
+        if self.statuses_write.get(hash).unwrap_option().is_some_and(|s| s.is_valid()) {
+            // We set the status to header-only only if it was previously set to a valid
+            // status. This is important since some proof headers might not have their status set
+            // and we would like to preserve this semantic (having a valid status implies that
+            // other parts of the code assume the existence of GD data etc.)
+            self.statuses_write.set_batch(self.batch, hash, StatusHeaderOnly).unwrap();
+        }
+
+        // Delete level-x relations for blocks which only belong to higher-than-x proof levels.
+        // This preserves the semantic that for each level, relations represent a contiguous DAG area in that level
+        for lower_level in 0..affiliated_level as usize {
+            relations::delete_level_relations(MemoryWriter, &mut self.staging_level_relations[lower_level], hash).unwrap_option();
+
+            if lower_level == 0 {
+                self.storage.ghostdag_store.delete_batch(self.batch, hash).unwrap_option();
+            }
+        }
+        self.stats.header_deletes_duration += start.elapsed();
+    }
+
+    fn delete_header_data(&mut self, hash: Hash, keep_header: bool) {
+        let start = Instant::now();
+        // Prune data related to headers: relations, reachability, ghostdag
+        let mergeset = relations::delete_reachability_relations(
+            MemoryWriter, // Both stores are staging so we just pass a dummy writer
+            &mut *self.staging_relations,
+            &*self.staging_reachability,
+            hash,
+        );
+        reachability::delete_block(
+            &mut self.children_cache.wrap(&mut *self.staging_reachability),
+            hash,
+            &mut mergeset.iter().copied(),
+        )
+        .unwrap();
+        // Prefer the block level recorded in compact GHOSTDAG data and fall back to reading the full header for
+        // entries written before the level was recorded there (or which were never populated with it)
+        let block_level = self
+            .storage
+            .ghostdag_store
+            .get_compact_data(hash)
+            .unwrap_option()
+            .and_then(|gd| gd.block_level)
+            .unwrap_or_else(|| self.storage.headers_store.get_header_with_block_level(hash).unwrap().block_level);
+        (0..=block_level as usize).for_each(|level| {
+            relations::delete_level_relations(MemoryWriter, &mut self.staging_level_relations[level], hash).unwrap_option();
+        });
+
+        self.storage.ghostdag_store.delete_batch(self.batch, hash).unwrap_option();
+
+        // Remove additional header related data
+        self.storage.daa_excluded_store.delete_batch(self.batch, hash).unwrap();
+        self.storage.depth_store.delete_batch(self.batch, hash).unwrap();
+        // Remove status completely
+        self.statuses_write.delete_batch(self.batch, hash).unwrap();
+
+        if !keep_header {
+            // Prune the actual headers
+            self.storage.headers_store.delete_batch(self.batch, hash).unwrap();
+        }
+        self.stats.header_deletes_duration += start.elapsed();
+    }
+}
+
+impl StagedPruningStores<'_, '_> {
+    /// Deletes all data of an orphaned block, i.e., a block below the pruning point which is not reachable from ORIGIN
+    /// via the reachability tree. Unlike [`PruningTraversalStores::delete_header_data`], this tolerates partially missing
+    /// data and does not rely on the block being linked to the reachability tree
+    pub fn delete_orphan_data(&mut self, hash: Hash, keep_header: bool) {
+        self.evict_caches(hash);
+        self.delete_body_data(hash, None);
+
+        let start = Instant::now();
+        // The block might have only part of its relations, so we attempt deletion at each level and ignore missing entries
+        relations::delete_level_relations(MemoryWriter, &mut *self.staging_relations, hash).unwrap_option();
+        for staging_level_relations in self.staging_level_relations.iter_mut() {
+            relations::delete_level_relations(MemoryWriter, staging_level_relations, hash).unwrap_option();
+        }
+        if self.staging_reachability.has(hash).unwrap() {
+            let mut reachability = self.children_cache.wrap(&mut *self.staging_reachability);
+            // Detach the block from its tree parent if the latter still lists it as a child (e.g., when the parent is itself an
+            // orphan), so that no child reference outlives the block. Its tree children are orphans as well and are swept
+            // along, hence they are not reattached
+            let parent = reachability.get_parent(hash).unwrap();
+            if reachability.has(parent).unwrap() {
+                if let Some(index) = reachability.get_children(parent).unwrap().iter().position(|&child| child == hash) {
+                    reachability.replace_child(parent, hash, index, &[]).unwrap();
+                }
+            }
+            reachability.delete(hash).unwrap();
+        }
+
+        self.storage.ghostdag_store.delete_batch(self.batch, hash).unwrap_option();
+        self.storage.daa_excluded_store.delete_batch(self.batch, hash).unwrap();
+        self.storage.depth_store.delete_batch(self.batch, hash).unwrap();
+        self.statuses_write.delete_batch(self.batch, hash).unwrap();
+
+        if !keep_header {
+            self.storage.headers_store.delete_batch(self.batch, hash).unwrap();
+        }
+        self.stats.header_deletes_duration += start.elapsed();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{BlockHashMap, BlockHashSet};
+    use std::{
+        collections::{BTreeMap, VecDeque},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+    };
+
+    /// An in-memory fake of the pruning traversal stores, modeling a DAG whose reachability tree is given explicitly
+    #[derive(Default, Clone)]
+    struct MemoryPruningStores {
+        future_of_pruning_point: BlockHashSet,
+        tree_children: BlockHashMap<Vec<Hash>>,
+        tree_parents: BlockHashMap<Hash>,
+        past_pruning_points: BlockHashSet,
+        bodies: BlockHashSet,
+        acceptance_data: BlockHashSet,
+        blue_scores: BlockHashMap<u64>,
+        /// Maps each block to the lowest level at which it still has relations
+        relations_from_level: BlockHashMap<BlockLevel>,
+        headers: BlockHashSet,
+        statuses: BlockHashSet,
+        window_caches: BlockHashSet,
+    }
+
+    impl MemoryPruningStores {
+        fn add_block(&mut self, hash: Hash, tree_parent: Option<Hash>) {
+            if let Some(parent) = tree_parent {
+                self.tree_children.entry(parent).or_default().push(hash);
+                self.tree_parents.insert(hash, parent);
+            }
+            self.bodies.insert(hash);
+            self.acceptance_data.insert(hash);
+            // Blue scores simply follow the tree depth
+            let blue_score = tree_parent.and_then(|parent| self.blue_scores.get(&parent)).map_or(0, |&parent_score| parent_score + 1);
+            self.blue_scores.insert(hash, blue_score);
+            self.relations_from_level.insert(hash, 0);
+            self.headers.insert(hash);
+            self.statuses.insert(hash);
+            self.window_caches.insert(hash);
+        }
+    }
+
+    impl AntifutureWalkStore for MemoryPruningStores {
+        fn is_in_future_of(&self, _pruning_point: Hash, hash: Hash) -> bool {
+            self.future_of_pruning_point.contains(&hash)
+        }
+
+        fn tree_children(&mut self, hash: Hash) -> BlockHashes {
+            Arc::new(self.tree_children.get(&hash).cloned().unwrap_or_default())
+        }
+    }
+
+    impl PruningTraversalStores for MemoryPruningStores {
+        fn is_past_pruning_point(&self, hash: Hash) -> bool {
+            self.past_pruning_points.contains(&hash)
+        }
+
+        fn evict_caches(&mut self, hash: Hash) {
+            self.window_caches.remove(&hash);
+        }
+
+        fn blue_score(&self, hash: Hash) -> Option<u64> {
+            self.blue_scores.get(&hash).copied()
+        }
+
+        fn delete_body_data(&mut self, hash: Hash, retained_acceptance_blue_score: Option<u64>) {
+            self.bodies.remove(&hash);
+            if retained_acceptance_blue_score.is_none() {
+                self.acceptance_data.remove(&hash);
+            }
+        }
+
+        fn demote_to_header_only(&mut self, hash: Hash, affiliated_level: BlockLevel) {
+            self.relations_from_level.insert(hash, affiliated_level);
+        }
+
+        fn delete_header_data(&mut self, hash: Hash, keep_header: bool) {
+            // Similar to reachability deletion, the tree children of the deleted block are moved to its tree parent
+            let children = self.tree_children.remove(&hash).unwrap_or_default();
+            if let Some(parent) = self.tree_parents.remove(&hash) {
+                let siblings = self.tree_children.entry(parent).or_default();
+                siblings.retain(|&sibling| sibling != hash);
+                siblings.extend(children.iter().copied());
+                children.iter().for_each(|&child| {
+                    self.tree_parents.insert(child, parent);
+                });
+            }
+            self.relations_from_level.remove(&hash);
+            self.statuses.remove(&hash);
+            if !keep_header {
+                self.headers.remove(&hash);
+            }
+        }
+    }
+
+    const ROOT: u64 = 100;
+
+    /// A chain 1 <- 2 <- ... <- 8 where 6 is the new pruning point and 7, 8 are in its future.
+    /// Block 9 is a tree child of 4 which is in the pruning point anticone
+    fn build_test_dag() -> (MemoryPruningStores, PruningPlan) {
+        let h = Hash::from_u64_word;
+        let mut stores = MemoryPruningStores::default();
+        stores.add_block(h(1), Some(h(ROOT)));
+        for i in 2..=8 {
+            stores.add_block(h(i), Some(h(i - 1)));
+        }
+        stores.add_block(h(9), Some(h(4)));
+        stores.future_of_pruning_point.extend([h(6), h(7), h(8)]);
+        stores.past_pruning_points.insert(h(1));
+
+        let plan = PruningPlan {
+            keep_blocks: BlockHashSet::from_iter([h(6), h(9)]),
+            keep_relations: BlockHashMap::from_iter([(h(6), 0), (h(9), 0), (h(5), 0), (h(3), 2)]),
+            pruned_tips: vec![],
+            retain_all_headers: false,
+            retain_kept_window_caches: false,
+            acceptance_data_cutoff: None,
+        };
+        (stores, plan)
+    }
+
+    #[test]
+    fn test_traversal_prunes_expected_blocks() {
+        let h = Hash::from_u64_word;
+        let (mut stores, plan) = build_test_dag();
+
+        let mut queue = VecDeque::from_iter(stores.tree_children(h(ROOT)).iter().copied());
+        let mut outcomes = BlockHashMap::new();
+        while let Some(current) = queue.pop_front() {
+            outcomes.insert(current, visit_block(&mut stores, &plan, h(6), current, &mut queue));
+        }
+
+        let expected_outcomes = BlockHashMap::from_iter([
+            (h(1), TraversalOutcome::Pruned),
+            (h(2), TraversalOutcome::Pruned),
+            (h(3), TraversalOutcome::HeaderOnly),
+            (h(4), TraversalOutcome::Pruned),
+            (h(5), TraversalOutcome::HeaderOnly),
+            (h(9), TraversalOutcome::Kept),
+        ]);
+        // The pruning point (the root of a subtree in its own future) is not even visited
+        assert_eq!(outcomes, expected_outcomes);
+
+        // Full data is kept for the pruning point future and anticone only
+        assert_eq!(stores.bodies, BlockHashSet::from_iter([h(6), h(7), h(8), h(9)]));
+        // Relations are kept for header-only blocks as well, from their affiliated level
+        assert_eq!(
+            stores.relations_from_level,
+            BlockHashMap::from_iter([(h(3), 2), (h(5), 0), (h(6), 0), (h(7), 0), (h(8), 0), (h(9), 0)])
+        );
+        // Headers of fully pruned blocks are deleted except for past pruning points
+        assert_eq!(stores.headers, BlockHashSet::from_iter([h(1), h(3), h(5), h(6), h(7), h(8), h(9)]));
+    }
+
+    #[test]
+    fn test_traversal_with_spilled_queue() {
+        let h = Hash::from_u64_word;
+        let traverse = |spill_threshold: Option<usize>| {
+            let (mut stores, plan) = build_test_dag();
+            let mut queue = TraversalQueue::new(spill_threshold, BTreeMap::new());
+            queue.extend(stores.tree_children(h(ROOT)).iter().copied());
+            let mut visits = Vec::new();
+            while let Some(current) = queue.pop_front() {
+                visits.push((current, visit_block(&mut stores, &plan, h(6), current, &mut queue)));
+            }
+            (visits, queue.spilled_count(), stores)
+        };
+
+        let (expected_visits, expected_spilled, expected_stores) = traverse(None);
+        assert_eq!(expected_spilled, 0);
+        // A threshold of a single entry forces spilling of every tree branch
+        let (visits, spilled, stores) = traverse(Some(1));
+        assert!(spilled > 0);
+        // The visiting order, the outcomes and the resulting stores must all be identical
+        assert_eq!(visits, expected_visits);
+        assert_eq!(stores.bodies, expected_stores.bodies);
+        assert_eq!(stores.relations_from_level, expected_stores.relations_from_level);
+        assert_eq!(stores.headers, expected_stores.headers);
+    }
+
+    #[test]
+    fn test_traversal_retains_all_headers() {
+        let h = Hash::from_u64_word;
+        let (mut stores, mut plan) = build_test_dag();
+        plan.retain_all_headers = true;
+
+        let mut queue = VecDeque::from_iter(stores.tree_children(h(ROOT)).iter().copied());
+        while let Some(current) = queue.pop_front() {
+            visit_block(&mut stores, &plan, h(6), current, &mut queue);
+        }
+
+        // Bodies and relations are pruned exactly as in full pruning mode
+        assert_eq!(stores.bodies, BlockHashSet::from_iter([h(6), h(7), h(8), h(9)]));
+        assert_eq!(
+            stores.relations_from_level,
+            BlockHashMap::from_iter([(h(3), 2), (h(5), 0), (h(6), 0), (h(7), 0), (h(8), 0), (h(9), 0)])
+        );
+        // All headers are retained, while fully pruned blocks are left with no status
+        assert_eq!(stores.headers, BlockHashSet::from_iter((1..=9).map(h)));
+        assert_eq!(stores.statuses, BlockHashSet::from_iter([h(3), h(5), h(6), h(7), h(8), h(9)]));
+    }
+
+    #[test]
+    fn test_traversal_retains_kept_window_caches() {
+        let h = Hash::from_u64_word;
+        let traverse = |retain_kept_window_caches: bool| {
+            let (mut stores, mut plan) = build_test_dag();
+            plan.retain_kept_window_caches = retain_kept_window_caches;
+            let mut queue = VecDeque::from_iter(stores.tree_children(h(ROOT)).iter().copied());
+            while let Some(current) = queue.pop_front() {
+                visit_block(&mut stores, &plan, h(6), current, &mut queue);
+            }
+            stores.window_caches
+        };
+
+        // By default, cache entries are evicted for all visited blocks below the pruning point, including the kept anticone block 9
+        assert_eq!(traverse(false), BlockHashSet::from_iter([h(6), h(7), h(8)]));
+        // With the retain option, only the cache entries of pruned blocks are evicted
+        assert_eq!(traverse(true), BlockHashSet::from_iter([h(6), h(7), h(8), h(9)]));
+    }
+
+    #[test]
+    fn test_traversal_retains_acceptance_data_within_depth() {
+        let h = Hash::from_u64_word;
+        let traverse = |acceptance_data_cutoff: Option<u64>| {
+            let (mut stores, mut plan) = build_test_dag();
+            plan.acceptance_data_cutoff = acceptance_data_cutoff;
+            let mut queue = VecDeque::from_iter(stores.tree_children(h(ROOT)).iter().copied());
+            while let Some(current) = queue.pop_front() {
+                visit_block(&mut stores, &plan, h(6), current, &mut queue);
+            }
+            assert_consistent(&stores);
+            stores.acceptance_data
+        };
+
+        // By default, acceptance data is pruned along with the bodies of all pruned blocks
+        assert_eq!(traverse(None), BlockHashSet::from_iter([h(6), h(7), h(8), h(9)]));
+        // Blocks 4 and 5 are within the retention depth (blue scores 3 and 4) and keep their acceptance data although
+        // 4 is fully pruned and 5 is demoted to header-only
+        assert_eq!(traverse(Some(3)), BlockHashSet::from_iter([h(4), h(5), h(6), h(7), h(8), h(9)]));
+    }
+
+    #[test]
+    fn test_traversal_does_not_visit_future_subtrees() {
+        let h = Hash::from_u64_word;
+        let (mut stores, plan) = build_test_dag();
+        // Extend the pruning point future by a long chain below 8, and add a second future subtree whose root is a tree child
+        // of the pruned block 4 (i.e., it is in the DAG future of the pruning point but not in its reachability subtree)
+        for i in 10..=40 {
+            stores.add_block(h(i), Some(h(if i == 10 { 8 } else { i - 1 })));
+        }
+        stores.add_block(h(50), Some(h(4)));
+        for i in 51..=60 {
+            stores.add_block(h(i), Some(h(50)));
+        }
+        stores.future_of_pruning_point.extend((10..=40).chain(50..=60).map(h));
+
+        let mut queue = VecDeque::from_iter(stores.tree_children(h(ROOT)).iter().copied());
+        let mut visited = BlockHashSet::new();
+        while let Some(current) = queue.pop_front() {
+            assert_ne!(visit_block(&mut stores, &plan, h(6), current, &mut queue), TraversalOutcome::Skipped);
+            visited.insert(current);
+        }
+
+        // Exactly the blocks which are not in the pruning point future are visited, while all future data is kept
+        assert_eq!(visited, BlockHashSet::from_iter([h(1), h(2), h(3), h(4), h(5), h(9)]));
+        assert!(stores.future_of_pruning_point.iter().all(|hash| stores.bodies.contains(hash)));
+        assert_consistent(&stores);
+    }
+
+    #[test]
+    fn test_traversal_matches_antifuture_iterator() {
+        let h = Hash::from_u64_word;
+        let (mut stores, plan) = build_test_dag();
+        // Add a future subtree below the pruned block 4 and a wider anticone subtree below the kept block 9
+        for i in 50..=53 {
+            stores.add_block(h(i), Some(h(4)));
+        }
+        stores.future_of_pruning_point.extend((50..=53).map(h));
+        for i in 60..=65 {
+            stores.add_block(h(i), Some(h(if i % 2 == 0 { 9 } else { i - 1 })));
+        }
+
+        // The anti-future as walked over the untouched tree, while the traversal below deletes blocks as it goes
+        let expected = reachability::antifuture_iterator(stores.clone(), h(ROOT), h(6)).collect::<Vec<_>>();
+
+        // Walk in small batches with a spilling queue, similar to the processor
+        let mut queue = TraversalQueue::new(Some(2), BTreeMap::new());
+        reachability::extend_antifuture_walk(&mut stores, h(ROOT), h(6), &mut queue);
+        let mut walk = Vec::new();
+        while !queue.is_empty() {
+            visit_batch(
+                &mut stores,
+                &plan,
+                h(6),
+                &mut queue,
+                |_, visited| visited >= 3,
+                |current, outcome| {
+                    assert_ne!(outcome, TraversalOutcome::Skipped);
+                    walk.push(current);
+                },
+            );
+        }
+
+        assert_eq!(walk, expected);
+        assert_consistent(&stores);
+    }
+
+    /// Asserts that no data of a block outlives its header and that the reachability tree only refers to blocks with relations
+    fn assert_consistent(stores: &MemoryPruningStores) {
+        for hash in stores.bodies.iter().chain(stores.relations_from_level.keys()) {
+            assert!(stores.headers.contains(hash), "block {hash} has data but no header");
+        }
+        for (&parent, children) in stores.tree_children.iter() {
+            assert!(parent == Hash::from_u64_word(ROOT) || stores.relations_from_level.contains_key(&parent));
+            assert!(children.iter().all(|child| stores.relations_from_level.contains_key(child)));
+        }
+    }
+
+    #[test]
+    fn test_traversal_cancelled_at_batch_boundary_resumes() {
+        let h = Hash::from_u64_word;
+        let run_batches = |stores: &mut MemoryPruningStores, plan: &PruningPlan, cancel: &AtomicBool| {
+            let mut queue = TraversalQueue::new(None, BTreeMap::new());
+            queue.extend(stores.tree_children(h(ROOT)).iter().copied());
+            let mut last_pruned_marker = None;
+            while !queue.is_empty() {
+                // Each batch is staged on a copy of the stores, which replaces the committed state (along with the
+                // progress marker) only once the batch completes. Cancellation is checked at batch boundaries only
+                let mut staged = stores.clone();
+                let last_visited = visit_batch(
+                    &mut staged,
+                    plan,
+                    h(6),
+                    &mut queue,
+                    |_, visited| visited >= 2,
+                    |current, _| {
+                        // Signal cancellation in the middle of the second batch
+                        if current == h(3) {
+                            cancel.store(true, Ordering::Relaxed);
+                        }
+                    },
+                );
+                *stores = staged;
+                last_pruned_marker = last_visited;
+                if cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+            }
+            last_pruned_marker
+        };
+
+        // The reference result of an uninterrupted traversal
+        let (mut expected, plan) = build_test_dag();
+        let mut queue = VecDeque::from_iter(expected.tree_children(h(ROOT)).iter().copied());
+        while let Some(current) = queue.pop_front() {
+            visit_block(&mut expected, &plan, h(6), current, &mut queue);
+        }
+
+        let (mut stores, plan) = build_test_dag();
+        let cancel = AtomicBool::new(false);
+        // The second batch (blocks 3 and 4) completes even though cancellation was signaled while visiting block 3
+        assert_eq!(run_batches(&mut stores, &plan, &cancel), Some(h(4)));
+        assert_consistent(&stores);
+        assert_eq!(stores.bodies, BlockHashSet::from_iter([h(5), h(6), h(7), h(8), h(9)]));
+
+        // A subsequent run starts over from the root and completes the prune
+        let cancel = AtomicBool::new(false);
+        assert!(run_batches(&mut stores, &plan, &cancel).is_some());
+        assert_consistent(&stores);
+        assert_eq!(stores.bodies, expected.bodies);
+        assert_eq!(stores.relations_from_level, expected.relations_from_level);
+        assert_eq!(stores.headers, expected.headers);
+    }
+}
@@ -0,0 +1,212 @@
+use crate::model::stores::pruning_queue::DbPruningQueueStore;
+use kaspa_hashes::Hash;
+use std::collections::VecDeque;
+
+/// A backing store for the spilled segment of a [`TraversalQueue`]
+pub trait QueueSpillStore {
+    /// Writes `hashes` at the consecutive positions starting at `first_position`
+    fn write_range(&mut self, first_position: u64, hashes: &[Hash]);
+
+    /// Reads and removes `count` entries at the consecutive positions starting at `first_position`
+    fn take_range(&mut self, first_position: u64, count: usize) -> Vec<Hash>;
+}
+
+impl QueueSpillStore for DbPruningQueueStore {
+    fn write_range(&mut self, first_position: u64, hashes: &[Hash]) {
+        DbPruningQueueStore::write_range(self, first_position, hashes).unwrap()
+    }
+
+    fn take_range(&mut self, first_position: u64, count: usize) -> Vec<Hash> {
+        DbPruningQueueStore::take_range(self, first_position, count).unwrap()
+    }
+}
+
+/// The FIFO queue of the pruning traversal. When a spill threshold is set, the in-memory portion is bounded by
+/// (roughly twice) the threshold and the remaining entries are spilled to a [`QueueSpillStore`] in chunks of
+/// threshold size, to be read back once the in-memory head drains. With no threshold, this is a plain `VecDeque`.
+///
+/// Entries are ordered as: the in-memory head, then the spilled segment, then the pending (not yet spilled) tail
+pub struct TraversalQueue<S: QueueSpillStore> {
+    head: VecDeque<Hash>,
+    tail: Vec<Hash>,
+    spill_store: S,
+    spill_threshold: Option<usize>,
+    /// The positions `[spilled_start, spilled_end)` are currently held by the spill store
+    spilled_start: u64,
+    spilled_end: u64,
+    /// The overall number of entries spilled by pushes
+    spilled: u64,
+}
+
+impl<S: QueueSpillStore> TraversalQueue<S> {
+    pub fn new(spill_threshold: Option<usize>, spill_store: S) -> Self {
+        Self {
+            head: VecDeque::new(),
+            tail: Vec::new(),
+            spill_store,
+            spill_threshold: spill_threshold.map(|threshold| threshold.max(1)),
+            spilled_start: 0,
+            spilled_end: 0,
+            spilled: 0,
+        }
+    }
+
+    /// Resumes a queue persisted by [`Self::persist`], whose entries are held by `spill_store` at the positions `[start, end)`.
+    /// With a spill threshold the entries are read back lazily as usual, otherwise they are read into memory at once
+    pub fn resume(spill_threshold: Option<usize>, spill_store: S, start: u64, end: u64) -> Self {
+        let mut queue = Self::new(spill_threshold, spill_store);
+        if queue.spill_threshold.is_some() {
+            (queue.spilled_start, queue.spilled_end) = (start, end);
+        } else {
+            queue.head.extend(queue.spill_store.take_range(start, (end - start) as usize));
+        }
+        queue
+    }
+
+    /// Moves all queued entries, in order, to the spill store and returns the positions `[start, end)` holding them, so that
+    /// the queue can later be resumed (see [`Self::resume`]). Entries are moved in chunks of the spill threshold size (if
+    /// set), hence the memory bound of the queue is respected
+    pub fn persist(&mut self) -> (u64, u64) {
+        // Positions from `spilled_end` and on are free, since no entries are pushed while draining
+        let start = self.spilled_end;
+        let mut end = start;
+        let chunk_size = self.spill_threshold.unwrap_or(usize::MAX);
+        let mut chunk = Vec::new();
+        while let Some(hash) = self.pop_front() {
+            chunk.push(hash);
+            if chunk.len() >= chunk_size || self.is_empty() {
+                self.spill_store.write_range(end, &chunk);
+                end += chunk.len() as u64;
+                chunk.clear();
+            }
+        }
+        (start, end)
+    }
+
+    pub fn push_back(&mut self, hash: Hash) {
+        match self.spill_threshold {
+            // Entries go to the head only if nothing is ordered before them in the spilled segment or the tail
+            Some(threshold) if self.head.len() >= threshold || !self.tail.is_empty() || self.spilled_start < self.spilled_end => {
+                self.tail.push(hash);
+                if self.tail.len() >= threshold {
+                    self.spill_store.write_range(self.spilled_end, &self.tail);
+                    self.spilled_end += self.tail.len() as u64;
+                    self.spilled += self.tail.len() as u64;
+                    self.tail.clear();
+                }
+            }
+            _ => self.head.push_back(hash),
+        }
+    }
+
+    /// Returns the front entry, refilling the in-memory head from the spilled segment (or the tail) if it drained
+    pub fn front(&mut self) -> Option<Hash> {
+        if self.head.is_empty() {
+            if self.spilled_start < self.spilled_end {
+                let threshold = self.spill_threshold.expect("entries are spilled only if a threshold is set");
+                let count = threshold.min((self.spilled_end - self.spilled_start) as usize);
+                self.head.extend(self.spill_store.take_range(self.spilled_start, count));
+                self.spilled_start += count as u64;
+            } else {
+                self.head.extend(self.tail.drain(..));
+            }
+        }
+        self.head.front().copied()
+    }
+
+    pub fn pop_front(&mut self) -> Option<Hash> {
+        self.front()?;
+        self.head.pop_front()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_empty() && self.tail.is_empty() && self.spilled_start == self.spilled_end
+    }
+
+    pub fn len(&self) -> usize {
+        self.head.len() + self.tail.len() + (self.spilled_end - self.spilled_start) as usize
+    }
+
+    /// Returns the overall number of entries which were spilled to the store
+    pub fn spilled_count(&self) -> u64 {
+        self.spilled
+    }
+}
+
+impl<S: QueueSpillStore> Extend<Hash> for TraversalQueue<S> {
+    fn extend<T: IntoIterator<Item = Hash>>(&mut self, iter: T) {
+        for hash in iter {
+            self.push_back(hash);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    /// An in-memory spill store for tests
+    impl QueueSpillStore for BTreeMap<u64, Hash> {
+        fn write_range(&mut self, first_position: u64, hashes: &[Hash]) {
+            self.extend(hashes.iter().enumerate().map(|(i, &hash)| (first_position + i as u64, hash)));
+        }
+
+        fn take_range(&mut self, first_position: u64, count: usize) -> Vec<Hash> {
+            (first_position..first_position + count as u64).map(|position| self.remove(&position).unwrap()).collect()
+        }
+    }
+
+    #[test]
+    fn test_traversal_queue_preserves_fifo_order_when_spilling() {
+        let mut queue = TraversalQueue::new(Some(3), BTreeMap::new());
+        let mut expected = VecDeque::new();
+        let mut next = 1u64;
+        // Interleave pushes and pops such that the queue repeatedly spills and refills
+        for round in 0..20 {
+            for _ in 0..(round % 7) + 1 {
+                queue.push_back(Hash::from_u64_word(next));
+                expected.push_back(Hash::from_u64_word(next));
+                next += 1;
+            }
+            for _ in 0..(round % 4) {
+                assert_eq!(queue.pop_front(), expected.pop_front());
+            }
+            assert_eq!(queue.len(), expected.len());
+        }
+        while let Some(hash) = expected.pop_front() {
+            assert_eq!(queue.pop_front(), Some(hash));
+        }
+        assert!(queue.is_empty());
+        assert_eq!(queue.pop_front(), None);
+        assert!(queue.spilled_count() > 0);
+        assert!(queue.spill_store.is_empty());
+    }
+
+    #[test]
+    fn test_traversal_queue_persist_and_resume() {
+        for spill_threshold in [None, Some(1), Some(3)] {
+            let mut queue = TraversalQueue::new(spill_threshold, BTreeMap::new());
+            queue.extend((1..=20).map(Hash::from_u64_word));
+            for i in 1..=5 {
+                assert_eq!(queue.pop_front(), Some(Hash::from_u64_word(i)));
+            }
+            queue.extend((21..=25).map(Hash::from_u64_word));
+
+            let (start, end) = queue.persist();
+            assert!(queue.is_empty());
+            assert_eq!(end - start, 20);
+            assert_eq!(queue.spill_store.len(), 20, "only the persisted entries are held by the store");
+
+            // The resumed queue yields the remaining entries in order, while new entries are ordered after them
+            let mut resumed = TraversalQueue::resume(spill_threshold, std::mem::take(&mut queue.spill_store), start, end);
+            assert_eq!(resumed.len(), 20);
+            resumed.extend((26..=30).map(Hash::from_u64_word));
+            for i in 6..=30 {
+                assert_eq!(resumed.pop_front(), Some(Hash::from_u64_word(i)));
+            }
+            assert!(resumed.is_empty());
+            assert!(resumed.spill_store.is_empty());
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Chunked export of the pruning point UTXO set, allowing a syncing peer to download the set and to verify
+//! it incrementally against the pruning point UTXO commitment
+
+use super::processor::PruningProcessor;
+use crate::model::stores::{
+    headers::HeaderStoreReader, pruning::PruningStoreReader, pruning_utxoset::PruningUtxosetStores, utxo_set::UtxoSetStoreReader,
+};
+use kaspa_consensus_core::{
+    errors::consensus::{ConsensusError, ConsensusResult},
+    muhash::MuHashExtensions,
+    tx::{TransactionOutpoint, UtxoEntry},
+};
+use kaspa_database::prelude::{CachePolicy, DbSnapshot, StoreResultExtensions};
+use kaspa_hashes::Hash;
+use kaspa_muhash::MuHash;
+use std::sync::Arc;
+
+/// An opaque cursor for resuming a pruning point UTXO set export from where the previous chunk ended
+#[derive(Clone, Debug)]
+pub struct PruningPointUtxoSetCursor {
+    pruning_point: Hash,
+    last_outpoint: TransactionOutpoint,
+}
+
+/// A chunk of the pruning point UTXO set, along with the pruning point it belongs to and the expected UTXO commitment
+pub struct PruningPointUtxoSetChunk {
+    pub pruning_point: Hash,
+    pub utxo_commitment: Hash,
+    pub utxos: Vec<(TransactionOutpoint, UtxoEntry)>,
+    /// The cursor for obtaining the next chunk, or `None` if this is the last chunk
+    pub next: Option<PruningPointUtxoSetCursor>,
+}
+
+impl PruningPointUtxoSetChunk {
+    /// Folds the UTXOs of this chunk into `multiset`. Once all chunks were folded, the finalized
+    /// multiset is expected to equal `utxo_commitment`
+    pub fn fold_into(&self, multiset: &mut MuHash) {
+        for (outpoint, entry) in self.utxos.iter() {
+            multiset.add_utxo(outpoint, entry);
+        }
+    }
+}
+
+/// A point-in-time view of the pruning point UTXO set, see [`PruningProcessor::pruning_point_utxo_set_snapshot`]
+pub struct PruningPointUtxoSetSnapshot<'a> {
+    /// The chain block whose UTXO set is captured by the snapshot
+    pub utxoset_position: Hash,
+    /// A cache-less handle to the stores, so that reads neither observe nor pollute the live cache
+    stores: PruningUtxosetStores,
+    snapshot: DbSnapshot<'a>,
+}
+
+impl PruningPointUtxoSetSnapshot<'_> {
+    /// Iterates the UTXO set captured by this snapshot. Concurrent writes (including pruning point movements) are not observed
+    pub fn iter(&self) -> impl Iterator<Item = (TransactionOutpoint, Arc<UtxoEntry>)> + '_ {
+        self.stores.utxo_set.snapshot_iterator(&self.snapshot).map(|item| item.unwrap())
+    }
+}
+
+impl PruningProcessor {
+    /// Returns a consistent snapshot of the pruning point UTXO set along with the chain block it corresponds to.
+    ///
+    /// The pruning point UTXO set is moved one chain block at a time, where each step atomically writes the block UTXO
+    /// diff together with the updated UTXO set position. Hence a DB snapshot always captures the exact UTXO set of the
+    /// position it records. Taking the snapshot requires no locks, so it neither waits for nor delays an ongoing movement,
+    /// and the snapshot remains valid (and unaffected) while the movement proceeds.
+    ///
+    /// Note that if taken mid-movement, the returned position is a chain block between the previous and the new pruning
+    /// point. Callers which require the set of a pruning point should compare the position against it and retry later.
+    /// A live snapshot pins the DB state it captures, so it should not be held for longer than needed
+    pub fn pruning_point_utxo_set_snapshot(&self) -> PruningPointUtxoSetSnapshot<'_> {
+        let stores = PruningUtxosetStores::new(self.db.clone(), CachePolicy::Empty);
+        let snapshot = self.db.snapshot();
+        // A missing position key is assumed to indicate a consistent state (see recovery workflow)
+        let utxoset_position = stores
+            .snapshot_utxoset_position(&snapshot)
+            .unwrap_option()
+            .unwrap_or_else(|| self.pruning_point_store.read().pruning_point().unwrap());
+        PruningPointUtxoSetSnapshot { utxoset_position, stores, snapshot }
+    }
+
+    /// Returns the next chunk (of up to `chunk_size` entries) of the pruning point UTXO set, starting from `cursor`
+    /// or from the beginning of the set if `cursor` is `None`.
+    ///
+    /// Each chunk is read under the pruning UTXO set lock and is guaranteed to reflect the UTXO set of the returned
+    /// pruning point. If the pruning point moved since the cursor was obtained (or is currently moving), the export
+    /// is torn and [`ConsensusError::UnexpectedPruningPoint`] is returned, in which case it should restart from scratch
+    pub fn export_pruning_point_utxo_set_chunk(
+        &self,
+        cursor: Option<PruningPointUtxoSetCursor>,
+        chunk_size: usize,
+    ) -> ConsensusResult<PruningPointUtxoSetChunk> {
+        let pruning_point = self.pruning_point_store.read().pruning_point().unwrap();
+        if cursor.as_ref().is_some_and(|cursor| cursor.pruning_point != pruning_point) {
+            return Err(ConsensusError::UnexpectedPruningPoint);
+        }
+
+        let pruning_utxoset_read = self.pruning_utxoset_stores.read();
+        // A position which differs from the pruning point indicates the UTXO set is in the middle of a movement.
+        // Note that a missing position key is assumed to indicate a consistent state (see recovery workflow)
+        if pruning_utxoset_read.utxoset_position().unwrap_option().is_some_and(|position| position != pruning_point) {
+            return Err(ConsensusError::UnexpectedPruningPoint);
+        }
+        let skip_first = cursor.is_some();
+        let utxos = pruning_utxoset_read
+            .utxo_set
+            .seek_iterator(cursor.map(|cursor| cursor.last_outpoint), chunk_size, skip_first)
+            .map(|item| item.unwrap())
+            .collect::<Vec<_>>();
+        drop(pruning_utxoset_read);
+
+        let utxo_commitment = self.headers_store.get_header(pruning_point).unwrap().utxo_commitment;
+        let next = match utxos.last() {
+            Some(&(last_outpoint, _)) if utxos.len() == chunk_size => Some(PruningPointUtxoSetCursor { pruning_point, last_outpoint }),
+            _ => None,
+        };
+        Ok(PruningPointUtxoSetChunk { pruning_point, utxo_commitment, utxos, next })
+    }
+}
@@ -116,6 +116,20 @@ impl<
         (new_pruning_points, new_candidate)
     }
 
+    /// Returns the number of blue score units the given sink must still advance by before
+    /// [`Self::next_pruning_points_and_candidate_by_ghostdag_data`] yields a new pruning point, or `None` if such a movement
+    /// is already due. This is an estimate assuming a selected chain block exists at the first blue score of the next finality interval
+    pub fn blue_score_until_next_pruning_point_movement(
+        &self,
+        ghostdag_data: CompactGhostdagData,
+        current_pruning_point: Hash,
+    ) -> Option<u64> {
+        let pruning_point_bs = self.ghostdag_store.get_blue_score(current_pruning_point).unwrap();
+        // The lowest blue score of a chain block which can become the next pruning point
+        let next_pruning_point_bs = (self.finality_score(pruning_point_bs) + 1) * self.finality_depth;
+        (next_pruning_point_bs + self.pruning_depth).checked_sub(ghostdag_data.blue_score).filter(|&remaining| remaining > 0)
+    }
+
     // finality_score is the number of finality intervals passed since
     // the given block.
     fn finality_score(&self, blue_score: u64) -> u64 {
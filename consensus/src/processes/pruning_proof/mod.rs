@@ -344,8 +344,17 @@ impl PruningProofManager {
         }
     }
 
-    pub fn get_pruning_point_proof(&self) -> Arc<PruningPointProof> {
-        let pp = self.pruning_point_store.read().pruning_point().unwrap();
+    /// Returns the proof of the current pruning point. The last built proof is cached keyed by its pruning point, so
+    /// repeated calls (e.g., data pruning and peers requesting the proof during sync) share a single build. The cached
+    /// entry is explicitly invalidated on pruning point movement (see [`Self::invalidate_cached_pruning_point_data`]), and
+    /// is anyway never served for a pruning point other than its key
+    pub fn get_pruning_point_proof_cached(&self) -> Arc<PruningPointProof> {
+        self.get_pruning_point_proof_cached_for(self.pruning_point_store.read().pruning_point().unwrap())
+    }
+
+    /// Returns the proof of `pp`, sharing the cache of [`Self::get_pruning_point_proof_cached`]. Used for building the proof
+    /// of a new pruning point before it is committed, so that the following data prune reuses it
+    pub fn get_pruning_point_proof_cached_for(&self, pp: Hash) -> Arc<PruningPointProof> {
         let mut cache_lock = self.cached_proof.lock();
         if let Some(cache) = cache_lock.clone() {
             if cache.pruning_point == pp {
@@ -357,8 +366,14 @@ impl PruningProofManager {
         proof
     }
 
+    /// Returns the anticone and trusted data of the current pruning point, cached by pruning point similarly to
+    /// [`Self::get_pruning_point_proof_cached`]. Only data computed once the anticone is final (see below) is cached
     pub fn get_pruning_point_anticone_and_trusted_data(&self) -> ConsensusResult<Arc<PruningPointTrustedData>> {
-        let pp = self.pruning_point_store.read().pruning_point().unwrap();
+        self.get_pruning_point_anticone_and_trusted_data_for(self.pruning_point_store.read().pruning_point().unwrap())
+    }
+
+    /// Returns the anticone and trusted data of `pp`, sharing the cache of [`Self::get_pruning_point_anticone_and_trusted_data`]
+    pub fn get_pruning_point_anticone_and_trusted_data_for(&self, pp: Hash) -> ConsensusResult<Arc<PruningPointTrustedData>> {
         let mut cache_lock = self.cached_anticone.lock();
         if let Some(cache) = cache_lock.clone() {
             if cache.pruning_point == pp {
@@ -378,4 +393,23 @@ impl PruningProofManager {
             Err(ConsensusError::PruningPointInsufficientDepth)
         }
     }
+
+    /// Drops the cached proof and trusted data unless they belong to `new_pruning_point`. Expected to be called once a new
+    /// pruning point is committed, so that the data of the previous pruning point is released right away rather than on
+    /// the next rebuild, while data already built for the new pruning point (see [`Self::get_pruning_point_proof_cached_for`])
+    /// is kept.
+    ///
+    /// Note: a build which started before the movement might still cache its (previous pruning point) result after this
+    /// call. This is harmless since cached entries are only served if their key matches the current pruning point
+    pub fn invalidate_cached_pruning_point_data(&self, new_pruning_point: Hash) {
+        let mut cached_proof = self.cached_proof.lock();
+        if cached_proof.as_ref().is_some_and(|cache| cache.pruning_point != new_pruning_point) {
+            cached_proof.take();
+        }
+        drop(cached_proof);
+        let mut cached_anticone = self.cached_anticone.lock();
+        if cached_anticone.as_ref().is_some_and(|cache| cache.pruning_point != new_pruning_point) {
+            cached_anticone.take();
+        }
+    }
 }
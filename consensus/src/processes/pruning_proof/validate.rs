@@ -58,7 +58,7 @@ impl PruningProofManager {
         // Get the proof for the current consensus and recreate the stores for it
         // This is expected to be fast because if a proof exists, it will be cached.
         // If no proof exists, this is empty
-        let mut current_consensus_proof = self.get_pruning_point_proof();
+        let mut current_consensus_proof = self.get_pruning_point_proof_cached();
         if current_consensus_proof.is_empty() {
             // An empty proof can only happen if we're at genesis. We're going to create a proof for this case that contains the genesis header only
             let genesis_header = self.headers_store.get_header(self.genesis_hash).unwrap();
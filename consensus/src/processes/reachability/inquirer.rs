@@ -1,8 +1,9 @@
 use super::interval::Interval;
 use super::{tree::*, *};
 use crate::model::stores::reachability::{ReachabilityStore, ReachabilityStoreReader};
-use kaspa_consensus_core::blockhash;
+use kaspa_consensus_core::blockhash::{self, BlockHashes};
 use kaspa_hashes::Hash;
+use std::collections::VecDeque;
 
 /// Init the reachability store to match the state required by the algorithmic layer.
 /// The function first checks the store for possibly being initialized already.
@@ -184,6 +185,99 @@ pub fn is_dag_ancestor_of(store: &(impl ReachabilityStoreReader + ?Sized), this:
     }
 }
 
+/// Batched version of [`is_dag_ancestor_of`] which checks `this` against each of the `queried` blocks, returning the
+/// results in the order of `queried`. The interval of `this` and the intervals of its future covering set are read once
+/// and shared by all queries, so each query costs a single interval read and an in-memory binary search
+pub fn is_dag_ancestor_of_many(store: &(impl ReachabilityStoreReader + ?Sized), this: Hash, queried: &[Hash]) -> Result<Vec<bool>> {
+    let this_interval = store.get_interval(this)?;
+    let future_covering_set = store.get_future_covering_set(this)?;
+    let mut fcs_intervals = Vec::with_capacity(future_covering_set.len());
+    for &hash in future_covering_set.iter() {
+        fcs_intervals.push(store.get_interval(hash)?);
+    }
+    debug_assert!(fcs_intervals.windows(2).all(|w| w[0].end < w[1].start));
+
+    let mut results = Vec::with_capacity(queried.len());
+    for &hash in queried {
+        let interval = store.get_interval(hash)?;
+        // Same logic as `is_dag_ancestor_of`: a chain ancestry check followed by a search of the future covering set
+        let is_ancestor = this_interval.contains(interval)
+            || match fcs_intervals.binary_search_by_key(&interval.end, |fci| fci.start) {
+                Ok(_) => true,
+                Err(i) => i > 0 && fcs_intervals[i - 1].contains(interval),
+            };
+        results.push(is_ancestor);
+    }
+    Ok(results)
+}
+
+/// The store surface required for walking the anti-future of a block over the reachability tree (see [`antifuture_iterator`]).
+/// Implemented for any reachability store reader, and extended by the data pruning traversal so that it walks the exact
+/// same blocks in the exact same order
+pub trait AntifutureWalkStore {
+    /// Returns whether `hash` is in the future of `excluding_future_of` (or is `excluding_future_of` itself)
+    fn is_in_future_of(&self, excluding_future_of: Hash, hash: Hash) -> bool;
+
+    /// Returns the reachability tree children of `hash`
+    fn tree_children(&mut self, hash: Hash) -> BlockHashes;
+}
+
+impl<T: ReachabilityStoreReader + ?Sized> AntifutureWalkStore for &T {
+    fn is_in_future_of(&self, excluding_future_of: Hash, hash: Hash) -> bool {
+        is_dag_ancestor_of(*self, excluding_future_of, hash).unwrap()
+    }
+
+    fn tree_children(&mut self, hash: Hash) -> BlockHashes {
+        self.get_children(hash).unwrap()
+    }
+}
+
+/// A single step of the anti-future walk: pushes the tree children of `current` which are not in
+/// `future(excluding_future_of) ∪ {excluding_future_of}` to `queue`. Such children are the roots of tree subtrees which
+/// are entirely in the excluded future (tree descendants are also DAG descendants), so they are never queued
+pub fn extend_antifuture_walk(
+    store: &mut (impl AntifutureWalkStore + ?Sized),
+    current: Hash,
+    excluding_future_of: Hash,
+    queue: &mut impl Extend<Hash>,
+) {
+    let children = store.tree_children(current);
+    queue.extend(children.iter().copied().filter(|&child| !store.is_in_future_of(excluding_future_of, child)));
+}
+
+/// Returns an iterator over all strict tree descendants of `from` which are not in `future(excluding_future_of) ∪ {excluding_future_of}`.
+/// The reachability tree is walked breadth-first (top-down), and the subtree of a block in the excluded future is not descended
+/// into, since tree descendants are also DAG descendants. With `from = ORIGIN` this yields the entire anti-future of
+/// `excluding_future_of` in the order visited by data pruning (both are driven by [`extend_antifuture_walk`]).
+///
+/// Note: the store must not be modified while the iterator is alive
+pub fn antifuture_iterator<S: AntifutureWalkStore>(mut store: S, from: Hash, excluding_future_of: Hash) -> impl Iterator<Item = Hash> {
+    let mut queue = VecDeque::new();
+    extend_antifuture_walk(&mut store, from, excluding_future_of, &mut queue);
+    std::iter::from_fn(move || {
+        let current = queue.pop_front()?;
+        extend_antifuture_walk(&mut store, current, excluding_future_of, &mut queue);
+        Some(current)
+    })
+}
+
+/// Returns whether `hash` is reachable from ORIGIN by walking the reachability tree top-down, i.e., whether every block along
+/// its tree parent chain is listed as a child by its own parent. A block which fails this is detached from the tree (e.g., an
+/// orphan left behind by an interrupted prune), hence is never visited by a tree walk from ORIGIN
+pub fn is_reachable_from_origin(store: &(impl ReachabilityStoreReader + ?Sized), mut hash: Hash) -> Result<bool> {
+    while hash != blockhash::ORIGIN {
+        if !store.has(hash)? {
+            return Ok(false);
+        }
+        let parent = store.get_parent(hash)?;
+        if !store.has(parent)? || !store.get_children(parent)?.contains(&hash) {
+            return Ok(false);
+        }
+        hash = parent;
+    }
+    Ok(true)
+}
+
 /// Finds the tree child of `ancestor` which is also a chain ancestor of `descendant`.
 pub fn get_next_chain_ancestor(store: &(impl ReachabilityStoreReader + ?Sized), descendant: Hash, ancestor: Hash) -> Result<Hash> {
     if descendant == ancestor {
@@ -261,12 +355,15 @@ mod tests {
         model::stores::{
             children::ChildrenStore,
             reachability::{DbReachabilityStore, MemoryReachabilityStore, StagingReachabilityStore},
-            relations::{DbRelationsStore, MemoryRelationsStore, RelationsStore, StagingRelationsStore},
+            relations::{DbRelationsStore, MemoryRelationsStore, RelationsStore, RelationsStoreReader, StagingRelationsStore},
         },
         processes::reachability::{interval::Interval, tests::gen::generate_complex_dag},
     };
     use itertools::Itertools;
-    use kaspa_consensus_core::blockhash::ORIGIN;
+    use kaspa_consensus_core::{
+        blockhash::{BlockHashes, ORIGIN},
+        BlockHashSet,
+    };
     use kaspa_database::prelude::ConnBuilder;
     use kaspa_database::{create_temp_db, prelude::CachePolicy};
     use parking_lot::RwLock;
@@ -316,6 +413,112 @@ mod tests {
         store.validate_intervals(root).unwrap();
     }
 
+    #[test]
+    fn test_is_dag_ancestor_of_many() {
+        let mut reachability = MemoryReachabilityStore::new();
+        let mut relations = MemoryRelationsStore::new();
+        let mut builder = DagBuilder::new(&mut reachability, &mut relations);
+        builder.init();
+        builder.add_block(DagBlock::new(1.into(), vec![ORIGIN]));
+        // Block 3 plays the pruning point. Block 10 selects 8 (outside the future of 3) as its parent, so it is in
+        // the future of 3 only via the future covering set of 3 (through 7)
+        for (block, parents) in [
+            (2u64, vec![1u64]),
+            (3, vec![2]),
+            (4, vec![2]),
+            (5, vec![3, 4]),
+            (6, vec![4]),
+            (7, vec![5]),
+            (8, vec![6]),
+            (9, vec![4, 6]),
+            (10, vec![7, 8]),
+        ] {
+            builder.add_block(DagBlock::new(block.into(), parents.into_iter().map(Hash::from).collect()));
+        }
+        reachability.validate_intervals(ORIGIN).unwrap();
+
+        let pruning_point: Hash = 3.into();
+        let tips: Vec<Hash> = (1u64..=10).map(Hash::from).collect();
+        let batched = is_dag_ancestor_of_many(&reachability, pruning_point, &tips).unwrap();
+        let per_tip = tips.iter().map(|&tip| is_dag_ancestor_of(&reachability, pruning_point, tip).unwrap()).collect_vec();
+        assert_eq!(batched, per_tip);
+
+        let in_future = tips.iter().zip(batched).filter_map(|(&tip, is_in_future)| is_in_future.then_some(tip)).collect_vec();
+        assert_eq!(in_future, [3u64, 5, 7, 10].map(Hash::from).to_vec());
+        assert!(is_dag_ancestor_of_many(&reachability, pruning_point, &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_antifuture_iterator() {
+        let mut reachability = MemoryReachabilityStore::new();
+        let mut relations = MemoryRelationsStore::new();
+        let mut builder = DagBuilder::new(&mut reachability, &mut relations);
+        builder.init();
+        builder.add_block(DagBlock::new(1.into(), vec![ORIGIN]));
+        for (block, parents) in [
+            (2u64, vec![1u64]),
+            (3, vec![2]),
+            (4, vec![2]),
+            (5, vec![3, 4]),
+            (6, vec![4]),
+            (7, vec![5]),
+            (8, vec![6]),
+            (9, vec![4, 6]),
+            (10, vec![7, 8]),
+            (11, vec![9]),
+        ] {
+            builder.add_block(DagBlock::new(block.into(), parents.into_iter().map(Hash::from).collect()));
+        }
+        reachability.validate_intervals(ORIGIN).unwrap();
+
+        for excluded in (1u64..=11).map(Hash::from) {
+            let antifuture = antifuture_iterator(&reachability, ORIGIN, excluded).collect_vec();
+
+            // Brute force: a block is in the anti-future iff the excluded block is not in its inclusive past
+            let expected: BlockHashSet =
+                (1u64..=11).map(Hash::from).filter(|&block| !inclusive_past(&relations, block).contains(&excluded)).collect();
+            assert_eq!(antifuture.iter().copied().collect::<BlockHashSet>(), expected);
+            assert_eq!(antifuture.len(), expected.len(), "blocks are yielded once");
+
+            // The order matches a breadth-first walk from ORIGIN which skips (without descending into) the excluded future
+            let mut queue = VecDeque::from_iter(reachability.get_children(ORIGIN).unwrap().iter().copied());
+            let mut walk = Vec::new();
+            while let Some(current) = queue.pop_front() {
+                if !is_dag_ancestor_of(&reachability, excluded, current).unwrap() {
+                    queue.extend(reachability.get_children(current).unwrap().iter().copied());
+                    walk.push(current);
+                }
+            }
+            assert_eq!(antifuture, walk);
+        }
+    }
+
+    #[test]
+    fn test_is_reachable_from_origin() {
+        let mut reachability = MemoryReachabilityStore::new();
+        let mut relations = MemoryRelationsStore::new();
+        let mut builder = DagBuilder::new(&mut reachability, &mut relations);
+        builder.init();
+        builder.add_block(DagBlock::new(1.into(), vec![ORIGIN]));
+        for (block, parents) in [(2u64, vec![1u64]), (3, vec![2]), (4, vec![2]), (5, vec![3, 4]), (6, vec![5])] {
+            builder.add_block(DagBlock::new(block.into(), parents.into_iter().map(Hash::from).collect()));
+        }
+        assert!((1u64..=6).all(|block| is_reachable_from_origin(&reachability, block.into()).unwrap()));
+        assert!(!is_reachable_from_origin(&reachability, 7.into()).unwrap(), "an unknown block is not reachable");
+
+        // Detaching a block from its tree parent detaches its entire tree subtree
+        let detached = Hash::from(3u64);
+        let parent = reachability.get_parent(detached).unwrap();
+        let index = reachability.get_children(parent).unwrap().iter().position(|&child| child == detached).unwrap();
+        reachability.replace_child(parent, detached, index, &[]).unwrap();
+        // Intervals are left intact, so the (inclusive) tree subtree can still be queried via chain ancestry
+        for block in (1u64..=6).map(Hash::from) {
+            let in_subtree = is_chain_ancestor_of(&reachability, detached, block).unwrap();
+            assert_eq!(is_reachable_from_origin(&reachability, block).unwrap(), !in_subtree);
+        }
+        assert!(!is_reachable_from_origin(&reachability, detached).unwrap());
+    }
+
     #[derive(Clone)]
     pub struct DagTestCase {
         genesis: u64,
@@ -480,6 +683,88 @@ mod tests {
         }
     }
 
+    /// The reachability data and DAG parents of a single block, as compared by [`test_grouped_deletion_matches_one_by_one`]
+    type BlockReachabilityData = (Hash, Interval, Hash, BlockHashes, BlockHashes, BlockHashes);
+
+    /// Builds the DAG of `test` over fresh DB stores and then deletes the `deleted` blocks in order, staging the deletions
+    /// of every `group_size` consecutive blocks together and committing them at once (similarly to the pruning traversal).
+    /// Returns the resulting data of all remaining blocks, sorted by hash
+    fn delete_blocks_in_groups(test: &DagTestCase, deleted: &[u64], group_size: usize) -> Vec<BlockReachabilityData> {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let cache_policy = CachePolicy::Count(test.blocks.len() / 3);
+        let reachability = RwLock::new(DbReachabilityStore::new(db.clone(), cache_policy, cache_policy));
+        let mut relations = DbRelationsStore::with_prefix(db.clone(), &[], CachePolicy::Empty, CachePolicy::Empty);
+
+        {
+            let mut batch = WriteBatch::default();
+            let mut staging_reachability = StagingReachabilityStore::new(reachability.upgradable_read());
+            let mut staging_relations = StagingRelationsStore::new(&mut relations);
+            let mut builder = DagBuilder::new(&mut staging_reachability, &mut staging_relations);
+            builder.init();
+            builder.add_block(DagBlock::new(test.genesis.into(), vec![ORIGIN]));
+            for (block, parents) in test.blocks.iter() {
+                builder.add_block(DagBlock::new((*block).into(), parents.iter().map(|&i| i.into()).collect()));
+            }
+            let reachability_write = staging_reachability.commit(&mut batch).unwrap();
+            staging_relations.commit(&mut batch).unwrap();
+            db.write(batch).unwrap();
+            drop(reachability_write);
+        }
+
+        for group in deleted.chunks(group_size) {
+            let mut batch = WriteBatch::default();
+            let mut staging_reachability = StagingReachabilityStore::new(reachability.upgradable_read());
+            let mut staging_relations = StagingRelationsStore::new(&mut relations);
+            for &block in group {
+                DagBuilder::new(&mut staging_reachability, &mut staging_relations).delete_block(block.into());
+            }
+            // Later deletions of the group must observe the intermediate state staged by the former ones
+            staging_reachability.validate_intervals(ORIGIN).unwrap();
+            validate_relations(&staging_relations).unwrap();
+            let reachability_write = staging_reachability.commit(&mut batch).unwrap();
+            staging_relations.commit(&mut batch).unwrap();
+            db.write(batch).unwrap();
+            drop(reachability_write);
+        }
+
+        let reachability_read = reachability.read();
+        reachability_read.validate_intervals(ORIGIN).unwrap();
+        validate_relations(&relations).unwrap();
+        subtree(reachability_read.deref(), ORIGIN)
+            .into_iter()
+            .sorted()
+            .map(|hash| {
+                (
+                    hash,
+                    reachability_read.get_interval(hash).unwrap(),
+                    reachability_read.get_parent(hash).unwrap(),
+                    reachability_read.get_children(hash).unwrap(),
+                    reachability_read.get_future_covering_set(hash).unwrap(),
+                    relations.get_parents(hash).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_grouped_deletion_matches_one_by_one() {
+        let (genesis, blocks) = generate_complex_dag(2.0, 3.0, 50);
+        let test = DagTestCase {
+            genesis,
+            blocks,
+            expected_past_relations: Default::default(),
+            expected_anticone_relations: Default::default(),
+        };
+        // Delete the genesis and the lower part of the DAG in topological order, similarly to the pruning traversal
+        let deleted = once(test.genesis).chain(test.blocks.iter().take(30).map(|&(block, _)| block)).collect_vec();
+
+        let one_by_one = delete_blocks_in_groups(&test, &deleted, 1);
+        assert_eq!(one_by_one.len(), test.blocks.len() - 30 + 1); // Including ORIGIN
+        for group_size in [4, 7, deleted.len()] {
+            assert_eq!(one_by_one, delete_blocks_in_groups(&test, &deleted, group_size), "group size: {}", group_size);
+        }
+    }
+
     #[test]
     fn test_dag_building_and_removal() {
         let manual_test = DagTestCase {
@@ -1,4 +1,8 @@
-use crate::{cache::CachePolicy, db::DB, errors::StoreError};
+use crate::{
+    cache::CachePolicy,
+    db::{DbSnapshot, DB},
+    errors::StoreError,
+};
 
 use super::prelude::{Cache, DbKey, DbWriter};
 use kaspa_utils::mem_size::MemSizeEstimator;
@@ -86,6 +90,26 @@ where
         })
     }
 
+    /// Iterates the store as captured by `snapshot`, bypassing the cache (which might already reflect later writes)
+    pub fn snapshot_iterator<'a>(&'a self, snapshot: &'a DbSnapshot<'_>) -> impl Iterator<Item = KeyDataResult<TData>> + 'a
+    where
+        TKey: Clone + AsRef<[u8]>,
+        TData: DeserializeOwned,
+    {
+        let prefix_key = DbKey::prefix_only(&self.prefix);
+        let mut read_opts = ReadOptions::default();
+        read_opts.set_iterate_range(rocksdb::PrefixRange(prefix_key.as_ref()));
+        snapshot.iterator_opt(IteratorMode::From(prefix_key.as_ref(), Direction::Forward), read_opts).map(move |iter_result| {
+            match iter_result {
+                Ok((key, data_bytes)) => match bincode::deserialize(&data_bytes) {
+                    Ok(data) => Ok((key[prefix_key.prefix_len()..].into(), data)),
+                    Err(e) => Err(e.into()),
+                },
+                Err(e) => Err(e.into()),
+            }
+        })
+    }
+
     pub fn write(&self, mut writer: impl DbWriter, key: TKey, data: TData) -> Result<(), StoreError>
     where
         TKey: Clone + AsRef<[u8]>,
@@ -215,6 +239,11 @@ where
     pub fn prefix(&self) -> &[u8] {
         &self.prefix
     }
+
+    /// Manually compacts the key range of this store (see [`DB::compact_prefix`])
+    pub fn compact(&self) {
+        self.db.compact_prefix(&self.prefix);
+    }
 }
 
 #[cfg(test)]
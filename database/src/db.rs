@@ -19,6 +19,26 @@ impl DB {
     }
 }
 
+impl DB {
+    /// Manually compacts all keys starting with `prefix`, allowing the space of deleted entries to be reclaimed promptly
+    pub fn compact_prefix(&self, prefix: &[u8]) {
+        self.inner.compact_range(Some(prefix), prefix_upper_bound(prefix).as_deref());
+    }
+
+    /// Returns the overall size of the DB SST files, if available
+    pub fn total_sst_files_size(&self) -> Option<u64> {
+        self.inner.property_int_value(rocksdb::properties::TOTAL_SST_FILES_SIZE).ok().flatten()
+    }
+}
+
+/// Returns the smallest key greater than all keys starting with `prefix`, or `None` if there is no such key
+fn prefix_upper_bound(prefix: &[u8]) -> Option<Vec<u8>> {
+    let position = prefix.iter().rposition(|&b| b != u8::MAX)?;
+    let mut upper = prefix[..=position].to_vec();
+    upper[position] += 1;
+    Some(upper)
+}
+
 impl DerefMut for DB {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.inner
@@ -33,6 +53,9 @@ impl Deref for DB {
     }
 }
 
+/// A point-in-time read view of the DB. Reads through a snapshot observe every write batch either fully or not at all
+pub type DbSnapshot<'a> = rocksdb::SnapshotWithThreadMode<'a, DBWithThreadMode<MultiThreaded>>;
+
 /// Deletes an existing DB if it exists
 pub fn delete_db(db_dir: PathBuf) {
     if !db_dir.exists() {
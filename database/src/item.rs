@@ -1,5 +1,5 @@
 use crate::{
-    db::DB,
+    db::{DbSnapshot, DB},
     errors::StoreError,
     prelude::{DbSetAccess, ReadLock},
 };
@@ -42,6 +42,18 @@ impl<T> CachedDbItem<T> {
         }
     }
 
+    /// Reads the item as captured by `snapshot`, bypassing the cache (which might already reflect later writes)
+    pub fn read_from_snapshot(&self, snapshot: &DbSnapshot<'_>) -> Result<T, StoreError>
+    where
+        T: DeserializeOwned,
+    {
+        if let Some(bytes) = snapshot.get(&self.key)? {
+            Ok(bincode::deserialize(&bytes)?)
+        } else {
+            Err(StoreError::KeyNotFound(DbKey::prefix_only(&self.key)))
+        }
+    }
+
     pub fn write(&mut self, mut writer: impl DbWriter, item: &T) -> Result<(), StoreError>
     where
         T: Clone + Serialize,
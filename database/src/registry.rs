@@ -36,11 +36,18 @@ pub enum DatabaseStorePrefixes {
     UtxoMultisets = 26,
     VirtualUtxoset = 27,
     VirtualState = 28,
+    PruningQueue = 29,
 
     // ---- Decomposed reachability stores ----
     ReachabilityTreeChildren = 30,
     ReachabilityFutureCoveringSet = 31,
 
+    // ---- Pruning progress ----
+    LastPrunedBlock = 35,
+    RetainedAcceptanceData = 36,
+    PruningStatsHistory = 37,
+    PruningDeferredFrontier = 38,
+
     // ---- Ghostdag Proof
     TempGhostdag = 40,
     TempGhostdagCompact = 41,
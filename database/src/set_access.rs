@@ -181,6 +181,11 @@ where
         &self.prefix
     }
 
+    /// Manually compacts the key range of this store (see [`DB::compact_prefix`])
+    pub fn compact(&self) {
+        self.db.compact_prefix(&self.prefix);
+    }
+
     pub fn bucket_iterator(&self, key: TKey) -> impl Iterator<Item = Result<TData, StoreError>> + '_
     where
         TKey: Clone + AsRef<[u8]>,
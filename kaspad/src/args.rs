@@ -76,6 +76,8 @@ pub struct Args {
     pub perf_metrics: bool,
     pub perf_metrics_interval_sec: u64,
     pub block_template_cache_lifetime: Option<u64>,
+    pub override_pruning_depth: Option<u64>,
+    pub override_finality_depth: Option<u64>,
 
     #[cfg(feature = "devnet-prealloc")]
     pub num_prealloc_utxos: Option<u64>,
@@ -128,6 +130,8 @@ impl Default for Args {
             perf_metrics_interval_sec: 10,
             externalip: None,
             block_template_cache_lifetime: None,
+            override_pruning_depth: None,
+            override_finality_depth: None,
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: None,
@@ -159,6 +163,12 @@ impl Args {
         config.p2p_listen_address = self.listen.unwrap_or(ContextualNetAddress::unspecified());
         config.externalip = self.externalip.map(|v| v.normalize(config.default_p2p_port()));
         config.ram_scale = self.ram_scale;
+        if let Some(pruning_depth) = self.override_pruning_depth {
+            config.params.pruning_depth = pruning_depth;
+        }
+        if let Some(finality_depth) = self.override_finality_depth {
+            config.params.finality_depth = finality_depth;
+        }
 
         #[cfg(feature = "devnet-prealloc")]
         if let Some(num_prealloc_utxos) = self.num_prealloc_utxos {
@@ -358,6 +368,20 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
                 .value_parser(clap::value_parser!(u64))
                 .help("Interval in seconds for performance metrics collection."),
         )
+        .arg(
+            Arg::new("override-pruning-depth")
+                .long("override-pruning-depth")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Override the network pruning depth (devnet/simnet only). Useful for exercising pruning in short test runs"),
+        )
+        .arg(
+            Arg::new("override-finality-depth")
+                .long("override-finality-depth")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help("Override the network finality depth (devnet/simnet only)"),
+        )
         .arg(arg!(--"disable-upnp" "Disable upnp"))
         .arg(arg!(--"nodnsseed" "Disable DNS seeding for peers"))
         .arg(arg!(--"nogrpc" "Disable gRPC server"))
@@ -444,6 +468,8 @@ impl Args {
             perf_metrics_interval_sec: arg_match_unwrap_or::<u64>(&m, "perf-metrics-interval-sec", defaults.perf_metrics_interval_sec),
             // Note: currently used programmatically by benchmarks and not exposed to CLI users
             block_template_cache_lifetime: defaults.block_template_cache_lifetime,
+            override_pruning_depth: m.get_one::<u64>("override-pruning-depth").cloned(),
+            override_finality_depth: m.get_one::<u64>("override-finality-depth").cloned(),
             disable_upnp: arg_match_unwrap_or::<bool>(&m, "disable-upnp", defaults.disable_upnp),
             disable_dns_seeding: arg_match_unwrap_or::<bool>(&m, "nodnsseed", defaults.disable_dns_seeding),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
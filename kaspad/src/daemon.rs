@@ -2,7 +2,7 @@ use std::{fs, path::PathBuf, process::exit, sync::Arc, time::Duration};
 
 use async_channel::unbounded;
 use kaspa_consensus_core::{
-    config::ConfigBuilder,
+    config::{params::Params, ConfigBuilder},
     errors::config::{ConfigError, ConfigResult},
 };
 use kaspa_consensus_notify::{root::ConsensusNotificationRoot, service::NotifyService};
@@ -101,6 +101,15 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if args.override_pruning_depth.is_some() || args.override_finality_depth.is_some() {
+        if !(args.devnet || args.simnet) {
+            return Err(ConfigError::DepthOverrideOnNonDevnet);
+        }
+        let mut params = Params::from(args.network());
+        params.pruning_depth = args.override_pruning_depth.unwrap_or(params.pruning_depth);
+        params.finality_depth = args.override_finality_depth.unwrap_or(params.finality_depth);
+        params.validate_pruning_depths()?;
+    }
     Ok(())
 }
 
@@ -80,7 +80,14 @@ impl IbdFlow {
             if let Some(_guard) = self.ctx.try_set_ibd_running(self.router.key(), relay_block.header.daa_score) {
                 info!("IBD started with peer {}", self.router);
 
-                match self.ibd(relay_block).await {
+                // Defer data pruning throughout IBD so that it does not contend with block processing over DB IO.
+                // Note that the consensus instance is reobtained when reopening the gate since IBD might have
+                // replaced it with a committed staging consensus
+                self.ctx.consensus().unguarded_session().set_pruning_allowed(false);
+                let res = self.ibd(relay_block).await;
+                self.ctx.consensus().unguarded_session().set_pruning_allowed(true);
+
+                match res {
                     Ok(_) => info!("IBD with peer {} completed successfully", self.router),
                     Err(e) => {
                         info!("IBD with peer {} completed with error: {}", self.router, e);
@@ -125,6 +132,7 @@ impl IbdFlow {
                         self.ctx.on_pruning_point_utxoset_override();
                         // This will reobtain the freshly committed staging consensus
                         session = self.ctx.consensus().session().await;
+                        session.set_pruning_allowed(false);
                     }
                     Err(e) => {
                         info!("IBD with headers proof from {} was unsuccessful ({})", self.router, e);
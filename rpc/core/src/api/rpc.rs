@@ -469,6 +469,16 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetCurrentBlockColorRequest,
     ) -> RpcResult<GetCurrentBlockColorResponse>;
 
+    /// Requests the current pruning point, candidate and index, along with the progress of an in-progress data prune (if any)
+    async fn get_pruning_info(&self) -> RpcResult<GetPruningInfoResponse> {
+        self.get_pruning_info_call(None, GetPruningInfoRequest {}).await
+    }
+    async fn get_pruning_info_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetPruningInfoRequest,
+    ) -> RpcResult<GetPruningInfoResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -2666,6 +2666,58 @@ impl Deserializer for GetCurrentBlockColorResponse {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPruningInfoRequest {}
+
+impl Serializer for GetPruningInfoRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetPruningInfoRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPruningInfoResponse {
+    pub pruning_point: RpcHash,
+    pub candidate: RpcHash,
+    pub index: u64,
+    /// The last block visited by an in-progress (or interrupted) data prune, if any
+    pub last_pruned_point: Option<RpcHash>,
+}
+
+impl Serializer for GetPruningInfoResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcHash, &self.pruning_point, writer)?;
+        store!(RpcHash, &self.candidate, writer)?;
+        store!(u64, &self.index, writer)?;
+        store!(Option<RpcHash>, &self.last_pruned_point, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetPruningInfoResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let pruning_point = load!(RpcHash, reader)?;
+        let candidate = load!(RpcHash, reader)?;
+        let index = load!(u64, reader)?;
+        let last_pruned_point = load!(Option<RpcHash>, reader)?;
+
+        Ok(Self { pruning_point, candidate, index, last_pruned_point })
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Subscriptions & notifications
 // ----------------------------------------------------------------------------
@@ -3398,6 +3450,90 @@ impl Deserializer for NewBlockTemplateNotification {
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// PruningPointMovedNotification
+
+/// NotifyPruningPointMovedRequest registers this connection for pruningPointMoved notifications.
+///
+/// See: PruningPointMovedNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyPruningPointMovedRequest {
+    pub command: Command,
+}
+
+impl NotifyPruningPointMovedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+impl Serializer for NotifyPruningPointMovedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Command, &self.command, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyPruningPointMovedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let command = load!(Command, reader)?;
+        Ok(Self { command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyPruningPointMovedResponse {}
+
+impl Serializer for NotifyPruningPointMovedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyPruningPointMovedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// PruningPointMovedNotification is sent whenever the node advances its pruning point. The
+/// new pruning point index is its position in the sequence of past pruning points.
+///
+/// See: NotifyPruningPointMovedRequest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PruningPointMovedNotification {
+    pub old_pruning_point: RpcHash,
+    pub new_pruning_point: RpcHash,
+    pub new_pruning_point_index: u64,
+}
+
+impl Serializer for PruningPointMovedNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcHash, &self.old_pruning_point, writer)?;
+        store!(RpcHash, &self.new_pruning_point, writer)?;
+        store!(u64, &self.new_pruning_point_index, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for PruningPointMovedNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let old_pruning_point = load!(RpcHash, reader)?;
+        let new_pruning_point = load!(RpcHash, reader)?;
+        let new_pruning_point_index = load!(u64, reader)?;
+        Ok(Self { old_pruning_point, new_pruning_point, new_pruning_point_index })
+    }
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
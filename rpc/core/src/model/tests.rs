@@ -921,6 +921,22 @@ mod mockery {
 
     test!(GetCoinSupplyResponse);
 
+    impl Mock for GetPruningInfoRequest {
+        fn mock() -> Self {
+            GetPruningInfoRequest {}
+        }
+    }
+
+    test!(GetPruningInfoRequest);
+
+    impl Mock for GetPruningInfoResponse {
+        fn mock() -> Self {
+            GetPruningInfoResponse { pruning_point: mock(), candidate: mock(), index: mock(), last_pruned_point: mock() }
+        }
+    }
+
+    test!(GetPruningInfoResponse);
+
     impl Mock for PingRequest {
         fn mock() -> Self {
             PingRequest {}
@@ -1284,6 +1300,30 @@ mod mockery {
 
     test!(NewBlockTemplateNotification);
 
+    impl Mock for NotifyPruningPointMovedRequest {
+        fn mock() -> Self {
+            NotifyPruningPointMovedRequest { command: Command::Start }
+        }
+    }
+
+    test!(NotifyPruningPointMovedRequest);
+
+    impl Mock for NotifyPruningPointMovedResponse {
+        fn mock() -> Self {
+            NotifyPruningPointMovedResponse {}
+        }
+    }
+
+    test!(NotifyPruningPointMovedResponse);
+
+    impl Mock for PruningPointMovedNotification {
+        fn mock() -> Self {
+            PruningPointMovedNotification { old_pruning_point: mock(), new_pruning_point: mock(), new_pruning_point_index: mock() }
+        }
+    }
+
+    test!(PruningPointMovedNotification);
+
     impl Mock for SubscribeResponse {
         fn mock() -> Self {
             SubscribeResponse::new(mock())
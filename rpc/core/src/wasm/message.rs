@@ -898,6 +898,41 @@ try_from! ( args: GetCurrentBlockColorResponse, IGetCurrentBlockColorResponse, {
 
 // ---
 
+declare! {
+    IGetPruningInfoRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetPruningInfoRequest { }
+    "#,
+}
+
+try_from! ( args: IGetPruningInfoRequest, GetPruningInfoRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetPruningInfoResponse,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetPruningInfoResponse {
+        pruningPoint : HexString;
+        candidate : HexString;
+        index : bigint;
+        lastPrunedPoint? : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: GetPruningInfoResponse, IGetPruningInfoResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
 declare! {
     IGetDaaScoreTimestampEstimateRequest,
     r#"
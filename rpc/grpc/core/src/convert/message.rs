@@ -193,6 +193,11 @@ from!(item: &kaspa_rpc_core::NotifyNewBlockTemplateRequest, protowire::NotifyNew
 });
 from!(RpcResult<&kaspa_rpc_core::NotifyNewBlockTemplateResponse>, protowire::NotifyNewBlockTemplateResponseMessage);
 
+from!(item: &kaspa_rpc_core::NotifyPruningPointMovedRequest, protowire::NotifyPruningPointMovedRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyPruningPointMovedResponse>, protowire::NotifyPruningPointMovedResponseMessage);
+
 // ~~~
 
 from!(&kaspa_rpc_core::GetCurrentNetworkRequest, protowire::GetCurrentNetworkRequestMessage);
@@ -430,6 +435,17 @@ from!(item: RpcResult<&kaspa_rpc_core::GetCurrentBlockColorResponse>, protowire:
     Self { blue: item.blue, error: None }
 });
 
+from!(&kaspa_rpc_core::GetPruningInfoRequest, protowire::GetPruningInfoRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetPruningInfoResponse>, protowire::GetPruningInfoResponseMessage, {
+    Self {
+        pruning_point: item.pruning_point.to_string(),
+        candidate: item.candidate.to_string(),
+        index: item.index,
+        last_pruned_point: item.last_pruned_point.map_or(Default::default(), |x| x.to_string()),
+        error: None,
+    }
+});
+
 from!(&kaspa_rpc_core::PingRequest, protowire::PingRequestMessage);
 from!(RpcResult<&kaspa_rpc_core::PingResponse>, protowire::PingResponseMessage);
 
@@ -647,6 +663,11 @@ try_from!(item: &protowire::NotifyNewBlockTemplateRequestMessage, kaspa_rpc_core
 });
 try_from!(&protowire::NotifyNewBlockTemplateResponseMessage, RpcResult<kaspa_rpc_core::NotifyNewBlockTemplateResponse>);
 
+try_from!(item: &protowire::NotifyPruningPointMovedRequestMessage, kaspa_rpc_core::NotifyPruningPointMovedRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(&protowire::NotifyPruningPointMovedResponseMessage, RpcResult<kaspa_rpc_core::NotifyPruningPointMovedResponse>);
+
 // ~~~
 
 try_from!(&protowire::GetCurrentNetworkRequestMessage, kaspa_rpc_core::GetCurrentNetworkRequest);
@@ -917,6 +938,16 @@ try_from!(item: &protowire::GetCurrentBlockColorResponseMessage, RpcResult<kaspa
     }
 });
 
+try_from!(&protowire::GetPruningInfoRequestMessage, kaspa_rpc_core::GetPruningInfoRequest);
+try_from!(item: &protowire::GetPruningInfoResponseMessage, RpcResult<kaspa_rpc_core::GetPruningInfoResponse>, {
+    Self {
+        pruning_point: RpcHash::from_str(&item.pruning_point)?,
+        candidate: RpcHash::from_str(&item.candidate)?,
+        index: item.index,
+        last_pruned_point: if item.last_pruned_point.is_empty() { None } else { Some(RpcHash::from_str(&item.last_pruned_point)?) },
+    }
+});
+
 try_from!(&protowire::PingRequestMessage, kaspa_rpc_core::PingRequest);
 try_from!(&protowire::PingResponseMessage, RpcResult<kaspa_rpc_core::PingResponse>);
 
@@ -87,6 +87,7 @@ pub enum KaspadPayloadOps {
     GetFeeEstimate,
     GetFeeEstimateExperimental,
     GetCurrentBlockColor,
+    GetPruningInfo,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -97,6 +98,7 @@ pub enum KaspadPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyPruningPointMoved,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
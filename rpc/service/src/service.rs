@@ -399,6 +399,21 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
     }
 
+    async fn get_pruning_info_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetPruningInfoRequest,
+    ) -> RpcResult<GetPruningInfoResponse> {
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let info = session.async_get_pruning_info().await;
+        Ok(GetPruningInfoResponse {
+            pruning_point: info.pruning_point,
+            candidate: info.candidate,
+            index: info.index,
+            last_pruned_point: info.last_pruned_point,
+        })
+    }
+
     async fn get_block_call(&self, _connection: Option<&DynRpcConnection>, request: GetBlockRequest) -> RpcResult<GetBlockResponse> {
         // TODO: test
         let session = self.consensus_manager.consensus().session().await;
@@ -76,6 +76,7 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::PruningPointMovedNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -638,6 +639,7 @@ impl RpcApi for KaspaRpcClient {
             GetMempoolEntry,
             GetMetrics,
             GetPeerAddresses,
+            GetPruningInfo,
             GetServerInfo,
             GetSink,
             GetSinkBlueScore,
@@ -60,6 +60,7 @@ impl Router {
                 GetMetrics,
                 GetConnections,
                 GetPeerAddresses,
+                GetPruningInfo,
                 GetServerInfo,
                 GetSink,
                 GetSinkBlueScore,
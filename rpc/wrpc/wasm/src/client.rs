@@ -907,6 +907,10 @@ build_wrpc_wasm_bindgen_subscriptions!([
     /// New block template notification event is produced when a new block
     /// template is generated for mining in the Kaspa BlockDAG.
     NewBlockTemplate,
+    /// Manage subscription for a pruning point moved notification event.
+    /// Pruning point moved notification event is produced when the node
+    /// advances its pruning point in the Kaspa BlockDAG.
+    PruningPointMoved,
 ]);
 
 // Build RPC method invocation functions. This macro
@@ -948,6 +952,11 @@ build_wrpc_wasm_bindgen_interface!(
         /// network that the node can potentially connect to.
         /// Returned information: List of peer addresses.
         GetPeerAddresses,
+        /// Retrieves the current pruning point, pruning point candidate and
+        /// pruning point index of the Kaspa node.
+        /// Returned information: Pruning point, candidate, index and the
+        /// last block visited by an in-progress data prune (if any).
+        GetPruningInfo,
         /// Retrieves various metrics and statistics related to the
         /// performance and status of the Kaspa node.
         /// Returned information: Memory usage, CPU usage, network activity.
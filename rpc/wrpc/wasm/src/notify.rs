@@ -25,6 +25,7 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    PruningPointMoved = "pruning-point-moved",
 }
 
 /**
@@ -40,7 +41,8 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate 
+    | IPruningPointMoved;
 
 /**
  * RPC notification event data map.
@@ -59,6 +61,7 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "pruning-point-moved" : IPruningPointMoved,
 }
 
 /**
@@ -84,6 +87,7 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribePruningPointMoved},
  * 
  * @category Node RPC
  */
@@ -241,3 +245,18 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    IPruningPointMoved,
+    r#"
+    /**
+     * Pruning point moved notification event is produced when the node
+     * advances its pruning point in the Kaspa BlockDAG.
+     * 
+     * @category Node RPC
+     */
+    export interface IPruningPointMoved {
+        [key: string]: any;
+    }
+    "#,
+}
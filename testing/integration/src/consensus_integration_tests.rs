@@ -13,7 +13,7 @@ use kaspa_consensus::model::stores::block_transactions::{
 };
 use kaspa_consensus::model::stores::ghostdag::{GhostdagStoreReader, KType as GhostdagKType};
 use kaspa_consensus::model::stores::headers::HeaderStoreReader;
-use kaspa_consensus::model::stores::reachability::DbReachabilityStore;
+use kaspa_consensus::model::stores::reachability::{DbReachabilityStore, ReachabilityStoreReader};
 use kaspa_consensus::model::stores::relations::DbRelationsStore;
 use kaspa_consensus::model::stores::selected_chain::SelectedChainStoreReader;
 use kaspa_consensus::params::{
@@ -41,7 +41,7 @@ use kaspa_consensus_notify::service::NotifyService;
 use kaspa_consensusmanager::ConsensusManager;
 use kaspa_core::task::tick::TickService;
 use kaspa_core::time::unix_now;
-use kaspa_database::utils::get_kaspa_tempdir;
+use kaspa_database::utils::{get_kaspa_tempdir, DbLifetime};
 use kaspa_hashes::Hash;
 
 use crate::common;
@@ -76,6 +76,7 @@ use std::{
     future::Future,
     io::{BufRead, BufReader},
     str::{from_utf8, FromStr},
+    time::Duration,
 };
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -2005,3 +2006,182 @@ async fn payload_activation_test() {
     assert!(matches!(status, Ok(BlockStatus::StatusUTXOValid)));
     assert!(consensus.lkg_virtual_state.load().accepted_tx_ids.contains(&tx_id));
 }
+
+#[tokio::test]
+async fn prune_with_report_test() {
+    prune_with_report_test_impl(0).await
+}
+
+#[tokio::test]
+async fn prune_with_report_offloaded_writes_test() {
+    prune_with_report_test_impl(2).await
+}
+
+async fn prune_with_report_test_impl(pruning_db_writer_threads: usize) {
+    init_allocator_with_default_settings();
+    let config = pruning_test_config_builder().set_pruning_db_writer_threads(pruning_db_writer_threads).build();
+    let (_lifetime, consensus) = build_prunable_chain(config).await;
+    let pruning_point = consensus.pruning_point();
+
+    let report = consensus.prune_with_report(pruning_point).unwrap();
+    assert!(report.completed);
+    let stats = &report.stats;
+    assert_eq!(stats.pruning_point, pruning_point);
+    assert!(stats.pruned > 0);
+    assert!(stats.traversed >= stats.pruned);
+    assert!(stats.timestamp > 0);
+    for (phase, duration) in [
+        ("plan", stats.plan_duration),
+        ("tips and chain", stats.tips_and_chain_duration),
+        ("traversal", stats.traversal_duration),
+        ("GHOSTDAG updates", stats.ghostdag_updates_duration),
+        ("body deletes", stats.body_deletes_duration),
+        ("header deletes", stats.header_deletes_duration),
+        ("staging commits", stats.staging_commits_duration),
+        ("DB writes", stats.db_writes_duration),
+    ] {
+        assert!(duration > Duration::ZERO, "the {phase} duration was not recorded");
+        assert!(duration <= stats.total_duration, "the {phase} duration exceeds the total duration");
+    }
+    if pruning_db_writer_threads > 0 {
+        assert!(stats.offloaded_batches > 0, "no traversal batch was written in the background");
+    } else {
+        assert_eq!(stats.offloaded_batches, 0);
+    }
+    assert_eq!(consensus.recent_pruning_stats().last(), Some(stats));
+}
+
+#[tokio::test]
+async fn prune_with_soft_deadline_test() {
+    init_allocator_with_default_settings();
+    let (_lifetime, consensus) = build_prunable_chain(pruning_test_config_builder().build()).await;
+    let expected = consensus.prune_with_report(consensus.pruning_point()).unwrap().stats;
+
+    // A zero deadline stops each run after a single batch, hence the prune is time-sliced over many runs. Each run resumes from
+    // the frontier left by the previous one, so the slices add up to exactly the work of a single uninterrupted run
+    let config = pruning_test_config_builder().set_pruning_max_blocks_per_commit(8).set_pruning_soft_deadline(Duration::ZERO).build();
+    let (_lifetime, consensus) = build_prunable_chain(config).await;
+    let pruning_point = consensus.pruning_point();
+    let (mut slices, mut traversed, mut pruned) = (0, 0, 0);
+    loop {
+        let report = consensus.prune_with_report(pruning_point).unwrap();
+        assert!(report.stats.traversed > 0, "a deferred run must progress");
+        traversed += report.stats.traversed;
+        pruned += report.stats.pruned;
+        slices += 1;
+        if report.completed {
+            break;
+        }
+        assert!(consensus.recent_pruning_stats().is_empty(), "deferred runs are not recorded in the stats history");
+    }
+    assert!(slices > 1);
+    assert_eq!((traversed, pruned), (expected.traversed, expected.pruned));
+    assert_eq!(consensus.recent_pruning_stats().len(), 1);
+}
+
+#[tokio::test]
+async fn interrupted_prune_to_current_point_test() {
+    init_allocator_with_default_settings();
+    let config = pruning_test_config_builder().set_pruning_max_blocks_per_commit(8).set_pruning_soft_deadline(Duration::ZERO).build();
+    let (_lifetime, consensus) = build_prunable_chain(config).await;
+    let pruning_point = consensus.pruning_point();
+    let prune_until_completed = || while !consensus.prune_with_report(pruning_point).unwrap().completed {};
+
+    prune_until_completed();
+    assert_eq!(consensus.get_pruning_info().last_pruned_point, None);
+
+    // Re-prune up to the current pruning point (the same path as `PruningProcessingMessage::PruneToCurrentPoint`), which
+    // revisits the retained blocks and is interrupted by the deadline while the history root already equals the pruning point
+    assert!(!consensus.prune_with_report(pruning_point).unwrap().completed);
+    assert!(consensus.get_pruning_info().last_pruned_point.is_some());
+    consensus.reconcile_pruning_state().expect("an interrupted re-prune is resumable");
+
+    // Resuming completes the re-prune and clears the marker
+    prune_until_completed();
+    assert_eq!(consensus.get_pruning_info().last_pruned_point, None);
+    consensus.reconcile_pruning_state().unwrap();
+}
+
+fn pruning_test_config_builder() -> ConfigBuilder {
+    ConfigBuilder::new(DEVNET_PARAMS).skip_proof_of_work().edit_consensus_params(|p| {
+        p.ghostdag_k = 5;
+        p.pruning_proof_m = 16;
+        p.legacy_difficulty_window_size = 64;
+        p.legacy_timestamp_deviation_tolerance = 16;
+        p.new_timestamp_deviation_tolerance = 16;
+        p.sampled_difficulty_window_size = p.sampled_difficulty_window_size.min(32);
+        p.finality_depth = 128;
+        p.merge_depth = 128;
+        p.mergeset_size_limit = 32;
+        p.pruning_depth = p.anticone_finalization_depth();
+    })
+}
+
+/// Builds a chain long enough for the pruning point to advance, while keeping the pruning gate closed so that the
+/// data prune is deferred, and returns a consensus reopened over the same DB with the prune still to be performed.
+/// The returned consensus does not run its processors, so that no background prune competes with an explicit one
+async fn build_prunable_chain(config: Config) -> (DbLifetime, TestConsensus) {
+    let (lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+    {
+        let consensus = TestConsensus::with_db(db.clone(), &config, unbounded().0);
+        let wait_handles = consensus.init();
+        consensus.set_pruning_allowed(false);
+        let mut tip = config.genesis.hash;
+        for i in 1..=2 * config.pruning_depth {
+            let hash = Hash::from_u64_word(i);
+            consensus.add_utxo_valid_block_with_parents(hash, vec![tip], vec![]).await.unwrap();
+            tip = hash;
+        }
+        consensus.shutdown(wait_handles);
+    }
+
+    let mut config = config;
+    config.process_genesis = false;
+    let consensus = TestConsensus::with_db(db, &config, unbounded().0);
+    assert_ne!(consensus.pruning_point(), config.genesis.hash, "the pruning point is expected to have advanced");
+    (lifetime, consensus)
+}
+
+#[tokio::test]
+async fn prune_with_max_blocks_per_commit_test() {
+    init_allocator_with_default_settings();
+    // Prunes the same chain with deletions committed one block at a time, in small groups and in uncapped groups, and
+    // verifies that the reachability data left behind is identical regardless of the grouping
+    let mut results = Vec::new();
+    for max_blocks_per_commit in [Some(1), Some(16), None] {
+        let mut builder = pruning_test_config_builder();
+        if let Some(max_blocks_per_commit) = max_blocks_per_commit {
+            builder = builder.set_pruning_max_blocks_per_commit(max_blocks_per_commit);
+        }
+        let config = builder.build();
+        let (_lifetime, consensus) = build_prunable_chain(config.clone()).await;
+        let pruning_point = consensus.pruning_point();
+
+        let report = consensus.prune_with_report(pruning_point).unwrap();
+        assert!(report.completed);
+        assert!(report.stats.pruned > 0);
+
+        let reachability = consensus.reachability_store().read();
+        let remaining = std::iter::once(config.genesis.hash)
+            .chain((1..=2 * config.pruning_depth).map(Hash::from_u64_word))
+            .filter(|&hash| reachability.has(hash).unwrap())
+            .map(|hash| {
+                (
+                    hash,
+                    reachability.get_interval(hash).unwrap(),
+                    reachability.get_parent(hash).unwrap(),
+                    reachability.get_children(hash).unwrap().to_vec(),
+                    reachability.get_future_covering_set(hash).unwrap().to_vec(),
+                )
+            })
+            .collect_vec();
+        assert!(remaining.iter().any(|(hash, ..)| *hash == pruning_point));
+        results.push((max_blocks_per_commit, report.stats.pruned, remaining));
+    }
+
+    let (_, expected_pruned, expected_remaining) = &results[0];
+    for (max_blocks_per_commit, pruned, remaining) in &results[1..] {
+        assert_eq!(pruned, expected_pruned, "pruned count differs with max blocks per commit {max_blocks_per_commit:?}");
+        assert_eq!(remaining, expected_remaining, "reachability differs with max blocks per commit {max_blocks_per_commit:?}");
+    }
+}
@@ -11,8 +11,8 @@ use kaspa_hashes::Hash;
 use kaspa_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
-        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
-        SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
+        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointMovedScope, PruningPointUtxoSetOverrideScope,
+        Scope, SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
     },
 };
 use kaspa_rpc_core::{api::rpc::RpcApi, model::*, Notification};
@@ -493,6 +493,17 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::GetPruningInfo => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_pruning_info_call(None, GetPruningInfoRequest {}).await.unwrap();
+                    // The pruning point of a freshly started node is genesis, and no data prune is in progress
+                    assert_eq!(response.pruning_point, SIMNET_GENESIS.hash);
+                    assert_eq!(response.index, 0);
+                    assert_eq!(response.last_pruned_point, None);
+                })
+            }
+
             KaspadPayloadOps::Ping => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -717,6 +728,13 @@ async fn sanity_test() {
                         .unwrap();
                 })
             }
+            KaspadPayloadOps::NotifyPruningPointMoved => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, PruningPointMovedScope {}.into()).await.unwrap();
+                })
+            }
             KaspadPayloadOps::StopNotifyingUtxosChanged => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -379,6 +379,14 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_pruning_info_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetPruningInfoRequest,
+    ) -> RpcResult<GetPruningInfoResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 